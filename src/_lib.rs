@@ -12,14 +12,22 @@
 //! [DropWithOwnedFields]: `DropWithOwnedFields`
 #![doc = include_str!("../README.md")]
 #![no_std]
+#![cfg_attr(feature = "dropck-eyepatch", feature(dropck_eyepatch))]
 #![allow(unused_braces)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 /// The crate's prelude.
 pub
 mod prelude {
     #[doc(no_inline)]
+    #[cfg(feature = "proc-macros")]
+    pub use crate::drop_with_owned_fields;
     pub use crate::{
-        drop_with_owned_fields,
         DropWithOwnedFields,
         DestructuredFieldsOf,
     };
@@ -29,8 +37,8 @@ mod seal {
     #[diagnostic::on_unimplemented(
         message = "missing `#[drop_with_owned_fields]` annotation on this type",
     )]
-    #[allow(drop_bounds, nonstandard_style)]
-    pub trait drop_with_owned_fields_annotation : Drop {}
+    #[allow(nonstandard_style)]
+    pub trait drop_with_owned_fields_annotation {}
 }
 
 /// Trait introducing the `Foo -> FooඞFields` association.
@@ -46,6 +54,56 @@ trait DestructureFields : Sized + seal::drop_with_owned_fields_annotation {
     /// no [`Drop`] `impl`, thus).
     type Fields;
 
+    /// The names of the fields of [`Self::Fields`], in declaration order.
+    ///
+    /// Tuple `struct`s get their field indices stringified (`"0"`, `"1"`, …), and raw identifiers
+    /// are reported without their `r#` prefix.
+    ///
+    /// ```rust
+    /// use ::drop_with_owned_fields::prelude::*;
+    /// use ::drop_with_owned_fields::DestructureFields;
+    ///
+    /// #[drop_with_owned_fields(as _)]
+    /// struct Foo {
+    ///     a: u8,
+    ///     r#type: (),
+    /// }
+    ///
+    /// # #[drop_with_owned_fields]
+    /// # impl Drop for Foo { fn drop(Self { .. }: _) {} }
+    /// #
+    /// assert_eq!(Foo::FIELD_NAMES, ["a", "type"]);
+    /// ```
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Calls <code>visitor.[visit_field]\(name, \&field)</code> for each field of
+    /// [`Self::Fields`], in declaration order.
+    ///
+    /// [visit_field]: `VisitField::visit_field`
+    fn for_each_field_ref(&self, visitor: &mut impl VisitField);
+
+    /// Calls <code>visitor.[visit_field_mut]\(name, \&mut field)</code> for each field of
+    /// [`Self::Fields`], in declaration order.
+    ///
+    /// [visit_field_mut]: `VisitFieldMut::visit_field_mut`
+    fn for_each_field_mut(&mut self, visitor: &mut impl VisitFieldMut);
+
+    /// Backs the blanket <code>impl [FieldsRef]</code>.
+    #[doc(hidden)]
+    fn __fields_ref(&self) -> &Self::Fields;
+
+    /// Backs the blanket <code>impl [FieldsRef]</code>.
+    #[doc(hidden)]
+    fn __fields_mut(&mut self) -> &mut Self::Fields;
+
+    /// The fields, declared in the configured drop order, for the `impl Drop` sugar to move the
+    /// fields left over into.
+    #[doc(hidden)]
+    type __DropOrdered;
+
+    #[doc(hidden)]
+    fn __drop_ordered(fields: Self::Fields) -> Self::__DropOrdered;
+
     /// "Defuse" the `impl Drop` on `Self` and return a field-destructurable `struct` witness of it.
     ///
     /// Note that "the `impl Drop`" on a type is rather "the optional [`ExtraDropGlue`]" of such a
@@ -118,7 +176,7 @@ trait DestructureFields : Sized + seal::drop_with_owned_fields_annotation {
     ///
     /// This function shall be available on every
     /// [`#[drop_with_owned_fields]`][drop_with_owned_fields]-annotated type, actually, **but as an
-    /// _inherent_ `pub(crate) const fn` method; _not_ as a trait method!** ⚠️
+    /// _inherent_ `pub(crate) fn` method; _not_ as a trait method!** ⚠️
     ///
     /// The reason for this is so as to never be `pub`, to avoid soundness footguns with contrived
     /// APIs.
@@ -135,6 +193,113 @@ trait DestructureFields : Sized + seal::drop_with_owned_fields_annotation {
     }
 }
 
+/// Visitor of the fields of a [`#[drop_with_owned_fields]`][drop_with_owned_fields]-annotated
+/// type, through shared references.
+///
+/// See [`DestructureFields::for_each_field_ref()`].
+///
+/// Since it is generic over the type of each field, the visitor gets to query type-level
+/// information about it, such as its [`type_name()`][::core::any::type_name].
+///
+/// # Example
+///
+/// ```rust
+/// use ::core::any::type_name;
+/// use ::drop_with_owned_fields::prelude::*;
+/// use ::drop_with_owned_fields::{DestructureFields, VisitField};
+///
+/// #[drop_with_owned_fields(as struct FooFields)]
+/// struct Foo {
+///     a: u8,
+///     b: String,
+/// }
+///
+/// # #[drop_with_owned_fields]
+/// # impl Drop for Foo { fn drop(Self { .. }: _) {} }
+/// #
+/// struct Describe(Vec<String>);
+///
+/// impl VisitField for Describe {
+///     fn visit_field<T : ?Sized>(&mut self, name: &'static str, _: &T) {
+///         self.0.push(format!("{name}: {}", type_name::<T>()));
+///     }
+/// }
+///
+/// let foo: Foo = FooFields { a: 42, b: "".into() }.into();
+/// let mut describe = Describe(vec![]);
+/// foo.for_each_field_ref(&mut describe);
+/// assert_eq!(describe.0, ["a: u8", "b: alloc::string::String"]);
+/// ```
+pub
+trait VisitField {
+    fn visit_field<T : ?Sized>(&mut self, name: &'static str, field: &T);
+}
+
+/// Visitor of the fields of a [`#[drop_with_owned_fields]`][drop_with_owned_fields]-annotated
+/// type, through exclusive references.
+///
+/// See [`DestructureFields::for_each_field_mut()`], and [`VisitField`] for an example.
+pub
+trait VisitFieldMut {
+    fn visit_field_mut<T : ?Sized>(&mut self, name: &'static str, field: &mut T);
+}
+
+/// Access to the [`Fields`][DestructureFields::Fields] of any
+/// [`#[drop_with_owned_fields]`][drop_with_owned_fields]-annotated type, by reference.
+///
+/// Each such type is also
+/// <code>[Deref][::core::ops::Deref]\<Target = [DestructuredFieldsOf]\<Self\>\></code> (and,
+/// with the [`as_ref`][drop_with_owned_fields#as_ref] arg,
+/// <code>[AsRef]\<[DestructuredFieldsOf]\<Self\>\></code> and
+/// <code>[AsMut]\<[DestructuredFieldsOf]\<Self\>\></code>), but this trait is
+/// blanket-implemented for every [`DestructureFields`] type, which makes it handier to use in
+/// generic code.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::prelude::*;
+/// use ::drop_with_owned_fields::FieldsRef;
+///
+/// fn reset<T : FieldsRef>(value: &mut T)
+/// where
+///     T::Fields : Default,
+/// {
+///     *value.fields_mut() = <_>::default();
+/// }
+///
+/// #[drop_with_owned_fields(as struct FooFields)]
+/// #[derive(Default)]
+/// struct Foo {
+///     a: u8,
+/// }
+///
+/// # #[drop_with_owned_fields]
+/// # impl Drop for Foo { fn drop(Self { .. }: _) {} }
+/// #
+/// let mut foo = Foo::from(FooFields { a: 42 });
+/// reset(&mut foo);
+/// assert_eq!(foo.fields().a, 0);
+/// ```
+pub
+trait FieldsRef : DestructureFields {
+    /// A shared reference to the fields of `self`.
+    fn fields(&self) -> &Self::Fields;
+
+    /// An exclusive reference to the fields of `self`.
+    fn fields_mut(&mut self) -> &mut Self::Fields;
+}
+
+impl<T : DestructureFields> FieldsRef for T {
+    #[inline]
+    fn fields(&self) -> &Self::Fields {
+        self.__fields_ref()
+    }
+
+    #[inline]
+    fn fields_mut(&mut self) -> &mut Self::Fields {
+        self.__fields_mut()
+    }
+}
+
 /// The whole objective of this crate: to allow one to write an `impl Drop`-looking block, _but
 /// with owned access to the fields_.
 ///
@@ -170,6 +335,14 @@ trait DropWithOwnedFields : DestructureFields {
 pub
 type DestructuredFieldsOf<T : ?Sized + DestructureFields> = T::Fields;
 
+pub mod declarative;
+
+#[cfg(feature = "std")]
+pub mod on_panic;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 /// Annotation required on a type in order for [`DropWithOwnedFields`] to be `impl`ementable for it.
 ///
 /// The attribute shall then define a
@@ -231,21 +404,718 @@ type DestructuredFieldsOf<T : ?Sized + DestructureFields> = T::Fields;
 ///     }
 ///     ```
 ///
+/// ## `drop_order(…)`, `reverse_drop_order`, and `#[drop_with_owned_fields(drop_last)]`
+///
+/// The fields not moved out of the
+/// <code>[DestructuredFieldsOf]\<Self\></code> within the <code>impl [DropWithOwnedFields]</code>
+/// logic get dropped at the end of it, in declaration order, by default.
+///
+/// This order can be configured, independently of the order in which the fields are declared:
+///
+///   - `drop_order(c, a, …)` drops the listed fields first, in that order, and then the unlisted
+///     ones, in declaration order;
+///
+///   - `reverse_drop_order` drops the fields in reverse declaration order;
+///
+///   - a `#[drop_with_owned_fields(drop_last)]` field is dropped after all the others.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+/// # struct Pool; struct Connection;
+///
+/// #[drop_with_owned_fields(as _)]
+/// struct Client {
+///     #[drop_with_owned_fields(drop_last)]
+///     pool: Pool,
+///     connection: Connection,
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Client {
+///     fn drop(Self { .. }: _) {
+///         // `connection` gets dropped first, and then `pool`.
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// The companion `struct` itself is left as declared (so are thus its derives, and its
+/// construction): it is the `impl Drop` sugar which moves the fields left over into a hidden twin
+/// of it, declared in the configured drop order. Hence this only being supported for braced
+/// `struct`s, and requiring the `"drop-sugar"` Cargo feature (an `impl` of `DropWithOwnedFields`
+/// is then rejected). That order also applies to the fields of a [disarmed](#disarmable) value,
+/// whereas the [`.destructure_fields_disabling_impl_drop()`][defuse] ones, being those of the
+/// companion `struct`, get dropped in declaration order.
+///
+/// ## `disarmable`
+///
+/// [`.destructure_fields_disabling_impl_drop()`][defuse] requires owned access to the value.
+/// With this arg, a runtime flag is added to the annotated type, so as to be able to disable the
+/// <code>impl [DropWithOwnedFields]</code> logic through a mere `&mut` access, by generating the
+/// following inherent methods (capped at `pub(crate)` visibility, for the same reasons as
+/// [`.destructure_fields_disabling_impl_drop()`][defuse]):
+///
+///   - `.disarm()`, after which dropping the value merely drops its fields, structurally (in the
+///     configured `drop_order(…)`, if any);
+///   - `.rearm()`, to undo it;
+///   - `.is_armed()`.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as struct GuardFields, disarmable)]
+/// struct Guard {
+///     name: String,
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Guard {
+///     fn drop(Self { name }: _) {
+///         panic!("{name} was not disarmed!");
+///     }
+/// }
+///
+/// let mut guards: Vec<Guard> = vec![GuardFields { name: "guard".into() }.into()];
+/// guards[0].disarm();
+/// drop(guards); // ✅
+/// ```
+///
+/// [defuse]: `DestructureFields::destructure_fields_disabling_impl_drop`
+///
+/// ## `track_drops`
+///
+/// Records each run of the <code>impl [DropWithOwnedFields]</code> logic of the annotated type
+/// into the current `testing::DropTracker` of the thread, when the `"testing"` Cargo feature is
+/// enabled (otherwise, it is a no-op).
+///
+/// See the `testing` module for more info.
+///
+/// ## `on_panic = abort | catch | propagate`
+///
+/// What to do should the <code>impl [DropWithOwnedFields]</code> logic panic:
+///
+///   - `propagate` (the default): let the panic unwind out of the `impl Drop`, which drops the
+///     remaining owned fields along the way, as usual.
+///
+///     Note that should this happen during an unwinding, the process aborts, with a rather
+///     unhelpful double-panic message;
+///
+///   - `abort`: abort the process, after having emitted a panic message naming the type;
+///
+///   - `catch` (requires the `"std"` Cargo feature): catch the panic, and report it through the
+///     hook of the `on_panic` module (which, by default, prints it to `stderr`), so that it
+///     never escapes the `impl Drop`.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as _, on_panic = abort)]
+/// struct Guard {
+///     // …
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Guard {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## `trace` or `trace(level = "…")`
+///
+/// Requires the `"tracing"` Cargo feature.
+///
+/// Runs the <code>impl [DropWithOwnedFields]</code> logic of the annotated type within a
+/// [`tracing`](https://docs.rs/tracing) span named after the type, at the given level (`"trace"`
+/// by default), with a `kind` field which is either:
+///
+///   - `"unwinding"`, when dropped as part of a panic unwinding;
+///   - or `"implicit"` otherwise (an explicit `drop(value)` cannot be told apart from the value
+///     going out of scope).
+///
+/// Should a panic escape the <code>impl [DropWithOwnedFields]</code> logic, an `ERROR` event is
+/// emitted within that span.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as _, trace(level = "debug"))]
+/// struct Connection {
+///     // …
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Connection {
+///     fn drop(Self { .. }: _) {
+///         // events emitted here are within a `Connection` span.
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## `accessors`, `accessors(…)`, and `#[drop_with_owned_fields(get, get_mut, set)]`
+///
+/// Generates inherent accessors on `Foo`, for an API independent of the layout of the companion
+/// `struct` (and of the visibility of its fields): `fn field(&self) -> &Field`,
+/// `fn field_mut(&mut self) -> &mut Field`, and `fn set_field(&mut self, Field) -> Field` (which
+/// returns the previous value).
+///
+///   - `accessors` generates all three of them, for every field, with the visibility of the
+///     `struct`, unless a different one is given (_e.g._, `accessors(pub(crate))`);
+///
+///   - a field can be annotated with `#[drop_with_owned_fields(get, get_mut, set)]` (or any
+///     subset thereof) to only get those (with or without the `accessors` arg).
+///
+/// Only supported for `struct`s with named fields.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as pub struct ConfigFields, accessors)]
+/// pub struct Config {
+///     name: String,
+///     #[drop_with_owned_fields(get)]
+///     retries: u32,
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Config {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+///
+/// fn rename(config: &mut Config) {
+///     config.name_mut().push_str(" (renamed)");
+///     assert!(*config.retries() > 0);
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## `debug`
+///
+/// Dumps the expansion of the attribute, pretty-printed, to `stderr`, at compile time.
+///
+/// Should the `DROP_WITH_OWNED_FIELDS_DEBUG_DIR` env var be set, the expansion is instead written
+/// to a `<that directory>/<crate_name>-<StructName>-<hash>.rs` file, the hash being that of the
+/// expansion, so that same-named `struct`s (in different modules) do not overwrite each other's.
+/// Both only depend on the input tokens, which makes the directory suitable for snapshot testing
+/// (as a whole, since a changed expansion lands in a new file). Since changes to that env var
+/// alone do not trigger a re-expansion, a rebuild may need to be forced (_e.g._, by `touch`ing the
+/// annotated file).
+///
+/// ## `delegate(…)`
+///
+/// Generates, for each listed trait, an `impl Trait for Foo` forwarding to the `impl Trait for
+/// DestructuredFieldsOf<Foo>` (through `Deref{,Mut}`), with a
+/// `where DestructuredFieldsOf<Self> : Trait` bound.
+///
+///   - The well-known traits are: `Display`, `Debug`, and the other formatting traits, as well as
+///     `fmt::Write`, `Hash`, `AsRef<T>`, `AsMut<T>`, `Iterator`, `DoubleEndedIterator`,
+///     `ExactSizeIterator`, and `FusedIterator`; and, with the `"std"` Cargo feature, `Error`,
+///     `Read`, `BufRead`, `Write` (that of `io`), and `Seek`.
+///
+///     They are to be named either bare (_e.g._, `Display`, or `AsRef<[u8]>`), whatever is in
+///     scope, a bare `Write` being that of `io`; or through their `::core`, `::alloc`, or `::std`
+///     path (_e.g._, `::core::fmt::Write`). Any other path, such as `self::Display`, or
+///     `fmt::Display`, is assumed to name a `#[delegatable]` trait of the caller.
+///
+///   - Any other trait needs to be annotated with [`#[delegatable]`][`delegatable`].
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(
+///     as struct BufferFields,
+///     delegate(Display, AsRef<[u8]>),
+/// )]
+/// struct Buffer {
+///     bytes: Vec<u8>,
+/// }
+///
+/// impl ::core::fmt::Display for BufferFields {
+///     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+///         write!(f, "{} byte(s)", self.bytes.len())
+///     }
+/// }
+///
+/// impl AsRef<[u8]> for BufferFields {
+///     fn as_ref(&self) -> &[u8] {
+///         &self.bytes
+///     }
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Buffer {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+///
+/// let buffer = Buffer::from(BufferFields { bytes: vec![42] });
+/// assert_eq!(AsRef::<[u8]>::as_ref(&buffer), [42]);
+/// assert_eq!(buffer.to_string(), "1 byte(s)");
+/// ```
+///
+/// ## `as_ref`
+///
+/// Generates `AsRef<FooFields>` and `AsMut<FooFields>` impls, so that a `Foo` may be fed to APIs
+/// expecting an `impl AsRef<FooFields>` (or an `impl AsMut<FooFields>`).
+///
+/// ## `borrow`
+///
+/// Generates `Borrow<FooFields>` and `BorrowMut<FooFields>` impls, so that a `Foo` may be fed to
+/// APIs expecting an `impl Borrow<FooFields>`.
+///
+/// As per [the contract of `Borrow`][::core::borrow::Borrow], the `PartialEq`, `Eq`,
+/// `PartialOrd`, `Ord`, and `Hash` impls of `Foo` and `FooFields` have to agree with one another:
+/// the arg thus also generates those of `Foo`, forwarding to those of `FooFields` (when the
+/// latter exist, be they `derive`d on the annotated `struct`, or hand-written for `FooFields`).
+/// Hand-written impls of these traits for `Foo` are thus rejected. This makes it possible, for
+/// instance, to query a `HashMap<Foo, _>` with a `&FooFields` key.
+///
+/// Relatedly, a `derive`d `PartialEq`, `Eq`, `PartialOrd`, `Ord`, or `Hash`, even without the arg,
+/// is implemented for `Foo` in that same forwarding fashion; and `PartialEq` and `PartialOrd`
+/// also yield impls of it between `Foo` and `FooFields`, in both directions.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+/// use ::std::collections::HashMap;
+///
+/// #[drop_with_owned_fields(as struct KeyFields, borrow)]
+/// #[derive(PartialEq, Eq, Hash)]
+/// struct Key {
+///     id: u32,
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Key {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+///
+/// let map = HashMap::from([(Key::from(KeyFields { id: 42 }), "…")]);
+/// assert_eq!(map.get(&KeyFields { id: 42 }), Some(&"…"));
+/// assert!(*map.keys().next().unwrap() == KeyFields { id: 42 });
+/// ```
+///
+/// ## `crate = …`
+///
+/// Every path in the expansion goes through `::drop_with_owned_fields` by default, which does not
+/// resolve in crates using the attribute through a re-export, without depending on this crate
+/// directly. This arg, much like serde's `#[serde(crate = "…")]`, overrides that path.
+///
+/// The `impl Drop` sugar takes it as well, as its only arg.
+///
+/// ```rust
+/// # mod framework { pub mod dwof { pub use ::drop_with_owned_fields::*; } }
+/// use framework::dwof::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as struct FooFields, crate = crate::framework::dwof)]
+/// struct Foo {
+///     // …
+/// }
+///
+/// #[drop_with_owned_fields(crate = crate::framework::dwof)]
+/// impl Drop for Foo {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ## `#[non_exhaustive]`
+///
+/// Not an arg, but a `#[non_exhaustive]` annotation on the `struct` is honored: downstream crates
+/// may read its fields (through `Deref`), and drop it (running the `DropWithOwnedFields` logic),
+/// but not construct it, whatever the visibility of the `struct`. To that end:
+///
+///   - the `FooFields` (and `Foo`) themselves get to be `#[non_exhaustive]`;
+///   - the inherent `FooFields::into()` is, at most, `pub(crate)`;
+///   - there is no `impl From<FooFields> for Foo`, unless `Deserialize` is `derive`d (which needs
+///     it, and is a public constructor anyways).
+///
+/// Constructors of this crate, be it `derive`d ones (_e.g._, `Clone` or `Default`), or
+/// hand-written ones, are thus the only way for downstream crates to get such a value.
+///
+/// ## `#[cfg(…)]`, and name collisions
+///
+/// Not an arg either: the `#[cfg(…)]`s on the `struct` are carried over to every top-level item
+/// of the expansion (the re-exports, the helper module, and the `impl`s of the `derive`s and of
+/// `delegate(…)`), so that, _e.g._, `#[cfg(unix)]` and `#[cfg(not(unix))]` definitions of a same
+/// `struct` may live in the same module.
+///
+/// The helper module, `_Fooඞdrop_with_owned_fields`, is named after the `struct` alone, though,
+/// with no hygiene whatsoever (Rust does not apply `macro_rules!` hygiene to item names anyways):
+/// definitions of a same-named `struct` which are not `cfg`-exclusive, such as those of two
+/// invocations of a `macro_rules!` defining a `struct Foo` in a same module, thus collide, on top
+/// of the `struct`s themselves doing so. Give them distinct names, or put them in distinct
+/// modules.
+///
+/// ## `unsafe(may_dangle(…))` (nightly-only)
+///
+/// Requires the `"dropck-eyepatch"` Cargo feature.
+///
+/// The listed type parameters are then allowed to dangle when the fields get dropped (through an
+/// `unsafe impl` of this crate's hidden `#[may_dangle]` machinery), so that, much like for a
+/// `Vec<&'a T>`, a `Foo<&'a T>` be allowed to be dropped after the borrowed data has been.
+///
+/// ```rust ,ignore
+/// use ::drop_with_owned_fields::drop_with_owned_fields;
+///
+/// #[drop_with_owned_fields(as struct MyVecFields, unsafe(may_dangle(T)))]
+/// struct MyVec<T> {
+///     items: Vec<T>,
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl<T> Drop for MyVec<T> {
+///     fn drop(Self { items }: _) {
+///         drop(items);
+///     }
+/// }
+///
+/// let mut v = MyVec::from(MyVecFields { items: vec![] });
+/// let s = String::from("…");
+/// v.items.push(&s);
+/// // `s` is dropped before `v` ✅
+/// ```
+///
+/// Such type parameters are not allowed to be involved in any trait bound, but this check is
+/// insufficient in and of itself, hence the `unsafe`:
+///
+/// ### Safety
+///
+/// The <code>impl [DropWithOwnedFields]</code> logic must not access the values involving the
+/// `may_dangle` type parameters in any way other than by moving them around or dropping them.
+///
 /// See the [main `crate` docs for more info][`crate`].
+#[cfg(feature = "proc-macros")]
 pub use ::drop_with_owned_fields_proc_macros::drop_with_owned_fields;
 
+/// Annotation on a `trait` definition for it to be usable within the
+/// [`delegate(…)`][drop_with_owned_fields#delegate] arg of
+/// [`#[drop_with_owned_fields]`][drop_with_owned_fields].
+///
+/// It defines a hidden `macro_rules!` homonym of the `trait` (with the same visibility), which
+/// is what `delegate(path::to::Trait)` expands to: the `trait` and the `#[drop_with_owned_fields]`
+/// type may thus live in different modules, or even different crates.
+///
+/// Every item is forwarded to the `DestructuredFieldsOf<Self>` implementation, except for the
+/// methods with a default implementation which take `self` by value (or through a `self: Type`
+/// receiver), which keep said default implementation.
+///
+/// Not supported: generic or `unsafe` `trait`s, generic associated types, and required methods
+/// taking `self` by value.
+///
+/// ```rust
+/// use ::drop_with_owned_fields::{delegatable, drop_with_owned_fields};
+///
+/// #[delegatable]
+/// trait Shape {
+///     const SIDES: usize;
+///     fn area(&self) -> f64;
+///     fn scale(&mut self, factor: f64);
+/// }
+///
+/// #[drop_with_owned_fields(as struct SquareFields, delegate(Shape))]
+/// struct Square {
+///     side: f64,
+/// }
+///
+/// impl Shape for SquareFields {
+///     const SIDES: usize = 4;
+///     fn area(&self) -> f64 {
+///         self.side * self.side
+///     }
+///     fn scale(&mut self, factor: f64) {
+///         self.side *= factor;
+///     }
+/// }
+///
+/// #[drop_with_owned_fields]
+/// impl Drop for Square {
+///     fn drop(Self { .. }: _) {
+///         // …
+///     }
+/// }
+///
+/// let mut square = Square::from(SquareFields { side: 2.0 });
+/// square.scale(1.5);
+/// assert_eq!(square.area(), 9.0);
+/// assert_eq!(<Square as Shape>::SIDES, 4);
+/// ```
+///
+/// Since the `macro_rules!` of a `pub trait` is `#[macro_export]`ed, two `pub trait`s with the
+/// same name, in different modules of the same crate, cannot both be `#[delegatable]`.
+#[cfg(feature = "proc-macros")]
+pub use ::drop_with_owned_fields_proc_macros::delegatable;
+
 // macro internals
 #[doc(hidden)] /** Not part of the public API */ pub
 mod ඞ {
     pub use ::core; // or `std`
+    #[cfg(feature = "alloc")]
+    pub extern crate alloc;
+    #[cfg(feature = "std")]
+    pub extern crate std;
+    #[cfg(feature = "proc-macros")]
     pub use ::drop_with_owned_fields_proc_macros::ඞannihilate as annihilate;
     pub use crate::seal::drop_with_owned_fields_annotation;
 
-    pub union ConstTransmuteUnchecked<Src, Dst> {
-        pub src: ::core::mem::ManuallyDrop<Src>,
-        pub dst: ::core::mem::ManuallyDrop<Dst>,
+    #[inline]
+    pub fn record_custom_drop<T : ?Sized>() {
+        #[cfg(feature = "testing")]
+        crate::testing::record_custom_drop::<T>();
     }
+
+    /// Turns unwinding out of the `DropWithOwnedFields` logic into an abort, by panicking
+    /// during that very unwinding.
+    pub struct AbortOnUnwind(pub &'static str);
+
+    impl AbortOnUnwind {
+        #[inline]
+        pub fn defuse(self) {
+            ::core::mem::forget(self);
+        }
+    }
+
+    impl Drop for AbortOnUnwind {
+        fn drop(&mut self) {
+            panic!("the `DropWithOwnedFields` logic of `{}` panicked: aborting", self.0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub use crate::on_panic::catch_drop_panic;
+
+    #[allow(type_alias_bounds)]
+    pub type DropOrderedFieldsOf<Foo : crate::DestructureFields> = Foo::__DropOrdered;
+
+    /// Implemented by the `impl Drop` sugar, which is what honors a configured drop order.
+    #[diagnostic::on_unimplemented(
+        message = "configuring the drop order of `{Self}` requires the `impl Drop` sugar",
+        note = "\
+            write a `#[drop_with_owned_fields] impl Drop for …` rather than an `impl` of \
+            `DropWithOwnedFields`, since only the former drops the fields left over in the \
+            configured order\
+        ",
+    )]
+    pub trait DropSugar {}
+
+    #[inline]
+    pub fn assert_drop_sugar<Foo : ?Sized + DropSugar>() {}
+
+    #[cfg(feature = "tracing")]
+    pub use ::tracing;
+
+    /// The `kind` of drop being traced.
+    #[cfg(feature = "tracing")]
+    pub fn drop_kind() -> &'static str {
+        if ::std::thread::panicking() {
+            "unwinding"
+        } else {
+            "implicit"
+        }
+    }
+
+    /// Keeps the span of a traced drop entered, and reports panics escaping the
+    /// `DropWithOwnedFields` logic.
+    #[cfg(feature = "tracing")]
+    pub struct TracedDrop {
+        _entered: ::tracing::span::EnteredSpan,
+        completed: bool,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl TracedDrop {
+        #[inline]
+        pub fn enter(span: ::tracing::Span) -> Self {
+            Self { _entered: span.entered(), completed: false }
+        }
+
+        #[inline]
+        pub fn completed(mut self) {
+            self.completed = true;
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl Drop for TracedDrop {
+        fn drop(&mut self) {
+            if !self.completed {
+                ::tracing::error!("panic escaped the `DropWithOwnedFields` logic");
+            }
+        }
+    }
+
+    // The `unsafe` operations behind the expansion, which thus features none (and may be used
+    // within `#![forbid(unsafe_code)]` crates). Rather than the `ManuallyDrop<FooFields>` of the
+    // unsugaring in the crate docs, with an `unsafe` `ManuallyDrop::take()` in an `impl Drop for
+    // Foo`, a `Foo` has a `ManuallyDropFields<Foo>`, in charge of the `Drop`-ping: it alone can
+    // take its fields, and only does so once, by value.
+
+    /// The drop logic of a `#[drop_with_owned_fields]` type, run by its `ManuallyDropFields`.
+    pub trait DropFieldsHook : crate::DestructureFields {
+        fn drop_fields(fields: Self::Fields);
+    }
+
+    /// Whether the drop logic of a `ManuallyDropFields` is to run: `()` for always, or a
+    /// `disarmable` `bool`.
+    pub trait ArmedState : Copy {
+        const ARMED: Self;
+
+        fn is_armed(self) -> bool;
+    }
+
+    impl ArmedState for () {
+        const ARMED: Self = ();
+
+        #[inline]
+        fn is_armed(self) -> bool {
+            true
+        }
+    }
+
+    impl ArmedState for bool {
+        const ARMED: Self = true;
+
+        #[inline]
+        fn is_armed(self) -> bool {
+            self
+        }
+    }
+
+    /// `Foo` may dangle when its `ManuallyDropFields` get dropped, provided the `NoDangle` type
+    /// parameters (by default, `Foo` itself, _i.e._, all of them) do not.
+    ///
+    /// # Safety
+    ///
+    /// The `DropFieldsHook` of `Foo` must not access the values involving the type parameters
+    /// missing from `NoDangle` other than by moving them around or dropping them.
+    pub unsafe trait Eyepatch<NoDangle : ?Sized> {}
+
+    unsafe impl<Foo : ?Sized> Eyepatch<Foo> for Foo {}
+
+    /// The fields of a `Foo`, `Drop`ped through its `DropFieldsHook`.
+    pub struct ManuallyDropFields<Foo, Armed = (), NoDangle : ?Sized = Foo>
+    where
+        Foo : DropFieldsHook + Eyepatch<NoDangle>,
+        Armed : ArmedState,
+    {
+        fields: ::core::mem::ManuallyDrop<Foo::Fields>,
+        armed: Armed,
+        // `ManuallyDrop` hides ownership of the fields from dropck, which, with the eyepatch,
+        // would otherwise let the drop glue of the fields run on dangling data.
+        _owns_fields: ::core::marker::PhantomData<Foo::Fields>,
+        _no_dangle: ::core::marker::PhantomData<fn() -> *const NoDangle>,
+    }
+
+    impl<Foo, Armed, NoDangle : ?Sized> ManuallyDropFields<Foo, Armed, NoDangle>
+    where
+        Foo : DropFieldsHook + Eyepatch<NoDangle>,
+        Armed : ArmedState,
+    {
+        #[inline]
+        pub const fn new(fields: Foo::Fields) -> Self {
+            Self {
+                fields: ::core::mem::ManuallyDrop::new(fields),
+                armed: Armed::ARMED,
+                _owns_fields: ::core::marker::PhantomData,
+                _no_dangle: ::core::marker::PhantomData,
+            }
+        }
+
+        /// The `Foo -> Foo::Fields` conversion, which skips the drop logic.
+        #[inline]
+        pub fn into_inner(this: Self) -> Foo::Fields {
+            // Defuse our `Drop` impl.
+            let mut this = ::core::mem::ManuallyDrop::new(this);
+            unsafe {
+                ::core::mem::ManuallyDrop::take(&mut this.fields)
+            }
+        }
+    }
+
+    impl<Foo, NoDangle : ?Sized> ManuallyDropFields<Foo, bool, NoDangle>
+    where
+        Foo : DropFieldsHook + Eyepatch<NoDangle>,
+    {
+        #[inline]
+        pub const fn is_armed(this: &Self) -> bool {
+            this.armed
+        }
+
+        #[inline]
+        pub fn set_armed(this: &mut Self, armed: bool) {
+            this.armed = armed;
+        }
+    }
+
+    impl<Foo, Armed, NoDangle : ?Sized> ::core::ops::Deref for ManuallyDropFields<Foo, Armed, NoDangle>
+    where
+        Foo : DropFieldsHook + Eyepatch<NoDangle>,
+        Armed : ArmedState,
+    {
+        type Target = Foo::Fields;
+
+        #[inline]
+        fn deref(&self) -> &Foo::Fields {
+            &self.fields
+        }
+    }
+
+    impl<Foo, Armed, NoDangle : ?Sized> ::core::ops::DerefMut for ManuallyDropFields<Foo, Armed, NoDangle>
+    where
+        Foo : DropFieldsHook + Eyepatch<NoDangle>,
+        Armed : ArmedState,
+    {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut Foo::Fields {
+            &mut self.fields
+        }
+    }
+
+    macro_rules! impl_Drop {( $($unsafe:ident #[$may_dangle:ident])? ) => (
+        $($unsafe)? // see `Eyepatch`.
+        impl<$(#[$may_dangle])? Foo, Armed, NoDangle : ?Sized> Drop for ManuallyDropFields<Foo, Armed, NoDangle>
+        where
+            Foo : DropFieldsHook + Eyepatch<NoDangle>,
+            Armed : ArmedState,
+        {
+            #[inline]
+            fn drop(&mut self) {
+                let fields = unsafe {
+                    ::core::mem::ManuallyDrop::take(&mut self.fields)
+                };
+                if self.armed.is_armed() {
+                    Foo::drop_fields(fields);
+                } else {
+                    // Structurally, but in the `drop_order`, if any.
+                    drop(Foo::__drop_ordered(fields));
+                }
+            }
+        }
+    )}
+
+    #[cfg(not(feature = "dropck-eyepatch"))]
+    impl_Drop!();
+    #[cfg(feature = "dropck-eyepatch")]
+    impl_Drop!(unsafe #[may_dangle]);
 }
 
+// ASCII-only alias of `ඞ`, for the `ascii-idents` Cargo feature and the `declarative` macro.
+#[doc(hidden)] /** Not part of the public API */ pub
+use ඞ as __private;
+
 #[doc = include_str!("compile_fail_tests.md")]
 mod _compile_fail_tests {}
@@ -0,0 +1,523 @@
+//! A `macro_rules!` front-end to [`#[drop_with_owned_fields]`][crate::drop_with_owned_fields],
+//! for builds wishing to avoid proc-macros altogether (_e.g._, by disabling the `"proc-macros"`
+//! default Cargo feature).
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::drop_with_owned_fields::declarative::drop_with_owned_fields;
+//!
+//! drop_with_owned_fields! {
+//!     /// Runs `f` on drop.
+//!     #[as pub struct DeferFields, helper_mod = defer_helpers]
+//!     pub struct Defer<F : FnOnce()> {
+//!         f: F,
+//!     }
+//!
+//!     impl Drop {
+//!         fn drop(Self { f }: _) {
+//!             f();
+//!         }
+//!     }
+//! }
+//!
+//! impl<F : FnOnce()> Defer<F> {
+//!     pub fn new(f: F) -> Self {
+//!         DeferFields { f }.into()
+//!     }
+//! }
+//! #
+//! # fn main() {
+//! #     let called = ::core::cell::Cell::new(false);
+//! #     drop(Defer::new(|| called.set(true)));
+//! #     assert!(called.get());
+//! # }
+//! ```
+//!
+//! The emitted items are the same as those of the attribute (the helper module, the companion
+//! `struct`, and the [`DestructureFields`][crate::DestructureFields], `From`, and `Deref{,Mut}`
+//! `impl`s, as well as the `.destructure_fields_disabling_impl_drop()` method),
+//! and the optional trailing `impl Drop { … }` block is the equivalent of the `"drop-sugar"`
+//! `impl Drop`, only without repeating the generics (those of the `struct` are in scope).
+//! An ill-formed such block is rejected with the very same message as with the sugar (modulo the
+//! `drop_with_owned_fields!` prefix; see `tests/ui/`), albeit spanning the whole invocation.
+//!
+//! # Supported subset
+//!
+//!   - The companion `struct` has to be named explicitly, and so does the helper module, since
+//!     `macro_rules!` cannot forge identifiers: `#[as $vis struct FooFields, helper_mod = foo]`.
+//!
+//!     Doc comments must come before it, and any other attributes after it. The latter end up on
+//!     the companion `struct` only, `#[derive(…)]`s included: unlike with the attribute, `Foo`
+//!     itself does not get the `derive`d `impl`s (`Clone`, `Default`, `PartialEq`, …), which
+//!     thus have to be written by hand, _e.g._, as `FooFields::clone(self).into()`.
+//!
+//!   - Braced `struct`s only, whose field visibilities are either private, `pub(self)`,
+//!     `pub(super)`, `pub(crate)`, or `pub` (same for the `struct` itself).
+//!
+//!   - Generic lifetime parameters, without bounds, followed by generic type parameters with, at
+//!     most, one trait bound each. No `const` parameters, no defaults, no `where` clauses.
+//!
+//!   - Field types are copied as opaque `:ty`s, so, unlike with the attribute, a `Self` in them
+//!     ends up designating the companion `struct`: name the annotated type explicitly instead.
+//!
+//!   - No attribute args beyond the `as …` rename.
+
+/// See [the module docs][self].
+#[doc(inline)]
+pub use crate::__drop_with_owned_fields_declarative as drop_with_owned_fields;
+
+#[doc(hidden)] /** Not part of the public API */ #[macro_export]
+macro_rules! __drop_with_owned_fields_declarative {
+    // The `struct` visibility dictates those of the items in the helper module.
+    (
+        $(#[doc = $doc:expr])*
+        #[as $pub_Fields:vis struct $StructNameFields:ident, helper_mod = $helper:ident $(,)?]
+        $(#[$attr:meta])*
+        pub(crate) struct $($rest:tt)*
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @generics
+        {
+            docs: [$(#[doc = $doc])*],
+            fields_struct: [$pub_Fields $StructNameFields],
+            helper: $helper,
+            attrs: [$(#[$attr])*],
+            pub: [pub(crate)],
+            pub_super: [pub(crate)],
+            pub_capped_at_crate: [pub(crate)],
+            pub_into: [pub(crate)],
+        }
+        $($rest)*
+    });
+    (
+        $(#[doc = $doc:expr])*
+        #[as $pub_Fields:vis struct $StructNameFields:ident, helper_mod = $helper:ident $(,)?]
+        $(#[$attr:meta])*
+        pub(super) struct $($rest:tt)*
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @generics
+        {
+            docs: [$(#[doc = $doc])*],
+            fields_struct: [$pub_Fields $StructNameFields],
+            helper: $helper,
+            attrs: [$(#[$attr])*],
+            pub: [pub(super)],
+            pub_super: [pub(in super::super)],
+            pub_capped_at_crate: [pub(in super::super)],
+            pub_into: [pub(in super::super)],
+        }
+        $($rest)*
+    });
+    (
+        $(#[doc = $doc:expr])*
+        #[as $pub_Fields:vis struct $StructNameFields:ident, helper_mod = $helper:ident $(,)?]
+        $(#[$attr:meta])*
+        pub(self) struct $($rest:tt)*
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @generics
+        {
+            docs: [$(#[doc = $doc])*],
+            fields_struct: [$pub_Fields $StructNameFields],
+            helper: $helper,
+            attrs: [$(#[$attr])*],
+            pub: [pub(self)],
+            pub_super: [pub(super)],
+            pub_capped_at_crate: [pub(super)],
+            pub_into: [pub(self)],
+        }
+        $($rest)*
+    });
+    (
+        $(#[doc = $doc:expr])*
+        #[as $pub_Fields:vis struct $StructNameFields:ident, helper_mod = $helper:ident $(,)?]
+        $(#[$attr:meta])*
+        pub struct $($rest:tt)*
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @generics
+        {
+            docs: [$(#[doc = $doc])*],
+            fields_struct: [$pub_Fields $StructNameFields],
+            helper: $helper,
+            attrs: [$(#[$attr])*],
+            pub: [pub],
+            pub_super: [pub],
+            pub_capped_at_crate: [pub(crate)],
+            pub_into: [pub],
+        }
+        $($rest)*
+    });
+    (
+        $(#[doc = $doc:expr])*
+        #[as $pub_Fields:vis struct $StructNameFields:ident, helper_mod = $helper:ident $(,)?]
+        $(#[$attr:meta])*
+        struct $($rest:tt)*
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @generics
+        {
+            docs: [$(#[doc = $doc])*],
+            fields_struct: [$pub_Fields $StructNameFields],
+            helper: $helper,
+            attrs: [$(#[$attr])*],
+            pub: [],
+            pub_super: [pub(super)],
+            pub_capped_at_crate: [pub(super)],
+            pub_into: [],
+        }
+        $($rest)*
+    });
+
+    (
+        @generics
+        { $($state:tt)* }
+        $StructName:ident
+        $(<
+            $($lt:lifetime),* $(,)?
+            $($T:ident $(: $Bound:path)?),* $(,)?
+        >)?
+        {
+            $($fields:tt)*
+        }
+        $(
+            impl $($drop_impl:tt)*
+        )?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields
+        {
+            $($state)*
+            name: $StructName,
+            intro: [$($($lt,)* $($T $(: $Bound)?,)*)?],
+            fwd: [$($($lt,)* $($T,)*)?],
+            drop: [$($($drop_impl)*)?],
+        }
+        []
+        $($fields)*
+    });
+
+    // Field visibilities get bumped by one level, to account for the helper module.
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+        $(#[$fattr:meta])* pub(crate) $field:ident : $FieldTy:ty $(, $($rest:tt)*)?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields { $($state)* }
+        [$($done)* [$(#[$fattr])*] [pub(crate)] $field : $FieldTy,]
+        $($($rest)*)?
+    });
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+        $(#[$fattr:meta])* pub(super) $field:ident : $FieldTy:ty $(, $($rest:tt)*)?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields { $($state)* }
+        [$($done)* [$(#[$fattr])*] [pub(in super::super)] $field : $FieldTy,]
+        $($($rest)*)?
+    });
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+        $(#[$fattr:meta])* pub(self) $field:ident : $FieldTy:ty $(, $($rest:tt)*)?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields { $($state)* }
+        [$($done)* [$(#[$fattr])*] [pub(super)] $field : $FieldTy,]
+        $($($rest)*)?
+    });
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+        $(#[$fattr:meta])* pub $field:ident : $FieldTy:ty $(, $($rest:tt)*)?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields { $($state)* }
+        [$($done)* [$(#[$fattr])*] [pub] $field : $FieldTy,]
+        $($($rest)*)?
+    });
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+        $(#[$fattr:meta])* $field:ident : $FieldTy:ty $(, $($rest:tt)*)?
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @fields { $($state)* }
+        [$($done)* [$(#[$fattr])*] [pub(super)] $field : $FieldTy,]
+        $($($rest)*)?
+    });
+    (
+        @fields { $($state:tt)* } [$($done:tt)*]
+    ) => ($crate::__drop_with_owned_fields_declarative! {
+        @emit
+        $($state)*
+        fields: [$($done)*],
+    });
+
+    (
+        @emit
+        docs: [$(#[doc = $doc:expr])*],
+        fields_struct: [$pub_Fields:vis $StructNameFields:ident],
+        helper: $helper:ident,
+        attrs: [$(#[$attr:meta])*],
+        pub: [$($pub:tt)*],
+        pub_super: [$($pub_super:tt)*],
+        pub_capped_at_crate: [$($pub_capped_at_crate:tt)*],
+        pub_into: [$($pub_into:tt)*],
+        name: $StructName:ident,
+        intro: [$($IntroGenerics:tt)*],
+        fwd: [$($FwdGenerics:tt)*],
+        drop: [$($drop:tt)*],
+        fields: [$(
+            [$(#[$fattr:meta])*] [$($fvis:tt)*] $field:ident : $FieldTy:ty,
+        )*],
+    ) => (
+        #[doc(inline)]
+        $($pub)* use $helper::$StructName;
+
+        $pub_Fields use $helper::$StructNameFields;
+
+        mod $helper {
+            use super::*;
+
+            $(#[doc = $doc])*
+            $(#[$attr])*
+            $($pub_super)*
+            struct $StructNameFields<$($IntroGenerics)*> {
+                $(
+                    $(#[$fattr])*
+                    $($fvis)* $field : $FieldTy,
+                )*
+            }
+
+            $(#[doc = $doc])*
+            #[repr(transparent)]
+            $($pub_super)*
+            struct $StructName<$($IntroGenerics)*> {
+                manually_drop_fields: $crate::__private::ManuallyDropFields<Self>,
+            }
+
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                $crate::__private::DropFieldsHook
+            for
+                $StructName<$($FwdGenerics)*>
+            {
+                #[inline]
+                fn drop_fields(fields: Self::Fields) {
+                    <Self as $crate::DropWithOwnedFields>::drop(fields);
+                }
+            }
+
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                $crate::__private::drop_with_owned_fields_annotation
+            for
+                $StructName<$($FwdGenerics)*>
+            {}
+
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                $crate::DestructureFields
+            for
+                $StructName<$($FwdGenerics)*>
+            {
+                type Fields = $StructNameFields<$($FwdGenerics)*>;
+
+                const FIELD_NAMES: &'static [&'static str] = &[
+                    $(::core::stringify!($field)),*
+                ];
+
+                #[inline]
+                fn for_each_field_ref(
+                    &self,
+                    visitor: &mut impl $crate::VisitField,
+                )
+                {
+                    $(
+                        visitor.visit_field(
+                            ::core::stringify!($field),
+                            &self.manually_drop_fields.$field,
+                        );
+                    )*
+                }
+
+                #[inline]
+                fn for_each_field_mut(
+                    &mut self,
+                    visitor: &mut impl $crate::VisitFieldMut,
+                )
+                {
+                    $(
+                        visitor.visit_field_mut(
+                            ::core::stringify!($field),
+                            &mut self.manually_drop_fields.$field,
+                        );
+                    )*
+                }
+
+                #[inline]
+                fn __fields_ref(&self) -> &Self::Fields {
+                    &*self.manually_drop_fields
+                }
+
+                #[inline]
+                fn __fields_mut(&mut self) -> &mut Self::Fields {
+                    &mut *self.manually_drop_fields
+                }
+
+                type __DropOrdered = Self::Fields;
+
+                #[inline]
+                fn __drop_ordered(fields: Self::Fields) -> Self::__DropOrdered {
+                    fields
+                }
+            }
+
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                ::core::convert::From<
+                    $StructNameFields<$($FwdGenerics)*>,
+                >
+            for
+                $StructName<$($FwdGenerics)*>
+            {
+                #[inline]
+                fn from(this: $StructNameFields<$($FwdGenerics)*>)
+                  -> Self
+                {
+                    this.into()
+                }
+            }
+
+            impl<$($IntroGenerics)*> $StructNameFields<$($FwdGenerics)*> {
+                /// Wraps these fields back into the type with the `DropWithOwnedFields` logic.
+                #[inline]
+                #[must_use]
+                $($pub_into)*
+                const
+                fn into(self) -> $StructName<$($FwdGenerics)*> {
+                    $StructName {
+                        manually_drop_fields: $crate::__private::ManuallyDropFields::new(self),
+                    }
+                }
+            }
+
+            impl<$($IntroGenerics)*> $StructName<$($FwdGenerics)*> {
+                #[inline]
+                $($pub_capped_at_crate)*
+                fn destructure_fields_disabling_impl_drop(self: $StructName<$($FwdGenerics)*>)
+                  -> $StructNameFields<$($FwdGenerics)*>
+                {
+                    let $StructName { manually_drop_fields } = self;
+                    $crate::__private::ManuallyDropFields::into_inner(manually_drop_fields)
+                }
+            }
+
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                ::core::ops::Deref
+            for
+                $StructName<$($FwdGenerics)*>
+            {
+                type Target = $crate::DestructuredFieldsOf<Self>;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    &*self.manually_drop_fields
+                }
+            }
+            #[automatically_derived]
+            impl<$($IntroGenerics)*>
+                ::core::ops::DerefMut
+            for
+                $StructName<$($FwdGenerics)*>
+            {
+                #[inline]
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut *self.manually_drop_fields
+                }
+            }
+        }
+
+        $crate::__drop_with_owned_fields_declarative! {
+            @drop_impl
+            [$($IntroGenerics)*] [$StructName<$($FwdGenerics)*>]
+            $($drop)*
+        }
+    );
+
+    // The `impl Drop { … }` sugar, with the diagnostics of the `"drop-sugar"` one.
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+    ) => ();
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn drop(Self $pat:tt : _ $(,)?) $body:block
+        }
+    ) => (
+        impl<$($IntroGenerics)*>
+            $crate::DropWithOwnedFields
+        for
+            $Self
+        {
+            fn drop(
+                $crate::DestructuredFieldsOf::<Self> $pat
+                : $crate::DestructuredFieldsOf<Self>
+            )
+            $body
+        }
+    );
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn drop $args:tt $body:block
+            $($unexpected_item:tt)+
+        }
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: unexpected item"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn drop(Self $pat:tt : _, $($extraneous_arg:tt)+) $body:block
+        }
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: extraneous `fn` arg"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn drop(Self $pat:tt : $($Ty:tt)+) $body:block
+        }
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: expected `_`"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn drop($($arg:tt)*) $body:block
+        }
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: \
+        expected a `Self { fields… }` or `Self(fields…)` destructuring pattern"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {
+            fn $not_drop:ident $($rest:tt)*
+        }
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: expected `drop`"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        Drop {}
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: expected at least one `fn`"
+    });
+    (
+        @drop_impl
+        [$($IntroGenerics:tt)*] [$Self:ty]
+        $($not_a_drop_impl:tt)*
+    ) => (::core::compile_error! {
+        "`drop_with_owned_fields!`: expected a `Drop` impl"
+    });
+}
@@ -0,0 +1,132 @@
+//! Hook for the panics caught by `on_panic = catch`-annotated types.
+//!
+//! Requires the `"std"` Cargo feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::drop_with_owned_fields::{drop_with_owned_fields, on_panic};
+//!
+//! #[drop_with_owned_fields(as struct FlushOnDropFields, on_panic = catch)]
+//! struct FlushOnDrop {
+//!     // …
+//! }
+//!
+//! #[drop_with_owned_fields]
+//! impl Drop for FlushOnDrop {
+//!     fn drop(Self { .. }: _) {
+//!         panic!("could not flush");
+//!     }
+//! }
+//!
+//! fn main() {
+//!     on_panic::set_hook(Box::new(|caught| {
+//!         assert_eq!(caught.message(), Some("could not flush"));
+//!     }));
+//!     drop(FlushOnDrop::from(FlushOnDropFields {}));
+//!     // Not reached if the panic were to propagate.
+//!     let _ = on_panic::take_hook();
+//! }
+//! ```
+#![allow(clippy::needless_doctest_main)]
+
+use ::core::{
+    any::Any,
+    fmt,
+};
+use ::alloc::{
+    boxed::Box,
+    string::String,
+    sync::Arc,
+};
+use ::std::{
+    eprintln,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::RwLock,
+};
+
+/// A panic having escaped the <code>impl [DropWithOwnedFields]</code> logic of an
+/// `on_panic = catch`-annotated type, and then caught.
+///
+/// [DropWithOwnedFields]: `crate::DropWithOwnedFields`
+pub
+struct CaughtPanic {
+    type_name: &'static str,
+    payload: Box<dyn Any + Send>,
+}
+
+impl CaughtPanic {
+    /// The name of the type whose drop logic panicked.
+    pub
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The payload of the panic, as given to [`::std::panic::resume_unwind()`].
+    pub
+    fn payload(&self) -> &(dyn Any + Send) {
+        &*self.payload
+    }
+
+    /// The panic message, when it is a string.
+    pub
+    fn message(&self) -> Option<&str> {
+        match self.payload.downcast_ref::<&'static str>() {
+            | Some(&msg) => Some(msg),
+            | None => self.payload.downcast_ref::<String>().map(|it| &it[..]),
+        }
+    }
+}
+
+impl fmt::Debug for CaughtPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaughtPanic")
+            .field("type_name", &self.type_name)
+            .field("message", &self.message())
+            .finish_non_exhaustive()
+    }
+}
+
+type Hook = dyn Fn(&CaughtPanic) + Sync + Send + 'static;
+
+/// An `Arc`, so that the hook may be called without holding the lock (the hook may then, itself,
+/// call [`set_hook()`], or panic, without deadlocking or poisoning anything).
+static HOOK: RwLock<Option<Arc<Hook>>> = RwLock::new(None);
+
+/// Registers the hook to be called with each [`CaughtPanic`], replacing the previous one.
+///
+/// The default hook prints the panic message to `stderr`.
+pub
+fn set_hook(hook: Box<dyn Fn(&CaughtPanic) + Sync + Send + 'static>) {
+    *HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(hook.into());
+}
+
+/// Unregisters the current hook (reverting to the default one), and returns it.
+pub
+fn take_hook() -> Box<dyn Fn(&CaughtPanic) + Sync + Send + 'static> {
+    let hook = HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+    match hook {
+        | Some(hook) => Box::new(move |caught: &CaughtPanic| hook(caught)),
+        | None => Box::new(default_hook),
+    }
+}
+
+fn default_hook(caught: &CaughtPanic) {
+    eprintln!(
+        "the `DropWithOwnedFields` logic of `{}` panicked: {}",
+        caught.type_name,
+        caught.message().unwrap_or("Box<dyn Any>"),
+    );
+}
+
+#[doc(hidden)] /** Not part of the public API */ pub
+fn catch_drop_panic(type_name: &'static str, drop_logic: impl FnOnce()) {
+    if let Err(payload) = catch_unwind(AssertUnwindSafe(drop_logic)) {
+        let caught = CaughtPanic { type_name, payload };
+        let hook = HOOK.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        match hook {
+            | Some(hook) => hook(&caught),
+            | None => default_hook(&caught),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //! Use https:://docs.rs/drop-with-owned-fields instead.
 // Templated by `cargo-generate` using https://github.com/danielhenrymantilla/proc-macro-template
 #![allow(nonstandard_style, unused_imports, unused_braces)]
+// Type- or trait-holding bindings get `UpperCase @ _`-named, so as not to be mistaken for unit
+// `struct` patterns: the `fn`s featuring them thus `#[allow(clippy::redundant_pattern)]`.
 
 use ::core::{
     mem,
@@ -25,6 +27,7 @@ use ::quote::{
     ToTokens,
 };
 use ::syn::{*,
+    ext::IdentExt,
     parse::{Parse, Parser, ParseStream},
     punctuated::Punctuated,
     Result, // Explicitly shadow it
@@ -39,13 +42,50 @@ use self::utils::default_to_mixed_site_span::{
 mod utils;
 use utils::{AlsoExt, BorrowedExt, Either, Retain};
 
+mod accessors;
+
 mod args;
 
+mod debug;
+
+mod delegatable;
+
+mod delegate;
+
 mod derives;
 
+mod drop_order;
+
+mod dropck_eyepatch;
+
 #[cfg(feature = "drop-sugar")]
 mod drop_sugar;
 
+mod self_ty;
+
+mod token_walking;
+
+/// Infix of the name of the helper module, `_Fooඞdrop_with_owned_fields`.
+///
+/// The ASCII-only variants (`ascii-idents` Cargo feature), for codebases denying
+/// `non_ascii_idents` (or whose tooling chokes on such identifiers), rely on the name of this
+/// crate, rather than on `ඞ`, to avoid collisions.
+const INFIX: &str = if cfg!(feature = "ascii-idents") { "__" } else { "ඞ" };
+
+/// Suffix of the name of the companion `struct`, `FooඞFields`, when not renamed (`as _`).
+const FIELDS_SUFFIX: &str = if cfg!(feature = "ascii-idents") {
+    "Fields__drop_with_owned_fields"
+} else {
+    "ඞFields"
+};
+
+/// Suffix of the name of the hidden, drop-ordered, twin of the companion `struct`.
+const DROP_ORDERED_SUFFIX: &str = if cfg!(feature = "ascii-idents") {
+    "DropOrdered__drop_with_owned_fields"
+} else {
+    "ඞDropOrdered"
+};
+
 #[proc_macro_attribute] /** Not part of the public API */ pub
 fn ඞannihilate(
     _: TokenStream,
@@ -55,6 +95,7 @@ fn ඞannihilate(
 }
 
 ///
+#[allow(clippy::empty_docs)] // the docs are those of the re-export.
 #[proc_macro_attribute] pub
 fn drop_with_owned_fields(
     args: TokenStream,
@@ -62,7 +103,6 @@ fn drop_with_owned_fields(
 ) -> TokenStream
 {
     drop_with_owned_fields_impl(args.into(), input.into())
-    //  .map(|ret| { println!("{}", ret); ret })
         .unwrap_or_else(|err| {
             let mut errors =
                 err .into_iter()
@@ -78,10 +118,34 @@ fn drop_with_owned_fields(
         .into()
 }
 
+///
+#[allow(clippy::empty_docs)] // the docs are those of the re-export.
+#[proc_macro_attribute] pub
+fn delegatable(
+    args: TokenStream,
+    input: TokenStream,
+) -> TokenStream
+{
+    delegatable::handle(args.into(), input.into())
+        .unwrap_or_else(|err| {
+            let mut errors =
+                err .into_iter()
+                    .map(|err| Error::new(
+                        err.span(),
+                        format_args!("`#[drop_with_owned_fields::delegatable]`: {}", err),
+                    ))
+            ;
+            let mut err = errors.next().unwrap();
+            errors.for_each(|cur| err.combine(cur));
+            err.to_compile_error()
+        })
+        .into()
+}
+
 enum Input {
     DeriveInput(DeriveInput),
     #[cfg(feature = "drop-sugar")]
-    ItemImpl(ItemImpl),
+    ItemImpl(Vec<Attribute>, TokenStream2),
 }
 
 impl Parse for Input {
@@ -101,9 +165,7 @@ impl Parse for Input {
             }
             #[cfg(feature = "drop-sugar")]
             {
-                let mut item_impl: ItemImpl = input.parse()?;
-                item_impl.attrs = attrs;
-                Ok(Self::ItemImpl(item_impl))
+                Ok(Self::ItemImpl(attrs, input.parse()?))
             }
         } else {
             let mut derive_input: DeriveInput = input.parse()?;
@@ -113,17 +175,31 @@ impl Parse for Input {
     }
 }
 
+// `let ref` bindings, for `#var` interpolations within `quote!`s.
+#[allow(clippy::redundant_pattern, clippy::toplevel_ref_arg)]
 fn drop_with_owned_fields_impl(
     args: TokenStream2,
     input: TokenStream2,
 ) -> Result<TokenStream2>
 {
-    let input = match parse2(input)? {
+    let mut input = match parse2(input)? {
         Input::DeriveInput(it) => it,
         #[cfg(feature = "drop-sugar")]
-        Input::ItemImpl(item_impl) => return drop_sugar::handle(args, item_impl),
+        Input::ItemImpl(attrs, item_impl) => return drop_sugar::handle(args, attrs, item_impl),
     };
+    let each_field_args = match &mut input.data {
+        | Data::Struct(DataStruct { fields, .. }) => {
+            fields
+                .iter_mut()
+                .map(|Field { attrs, .. }| args::FieldArgs::extract_from(attrs))
+                .collect::<Result<Vec<_>>>()?
+        },
+        | _ => vec![],
+    };
+    self_ty::rewrite_Self(&mut input)?;
     let ref args: args::Args = parse2(args)?;
+    let ref krate = args::crate_path(args.krate.as_ref());
+    let ref ඞ = args::internals_path(krate);
     let DeriveInput {
         vis: pub_,
         attrs,
@@ -137,6 +213,9 @@ fn drop_with_owned_fields_impl(
             .filter(|attr| attr.path().is_ident("doc"))
             .collect::<Vec<_>>()
     ;
+    // To be carried over to every top-level item of the expansion, lest, _e.g._, `#[cfg(unix)]`
+    // and `#[cfg(windows)]` definitions of a same `struct` yield conflicting helper modules.
+    let ref cfgs = utils::cfg_attrs(attrs);
     let DataStruct { fields, semi_token, .. } = match *data {
         | Data::Struct(ref it) => it,
         | Data::Enum(DataEnum { enum_token: token::Enum { span, .. }, .. })
@@ -146,16 +225,26 @@ fn drop_with_owned_fields_impl(
         },
     };
     fn super_of(pub_: &Visibility) -> Cow<'_, Visibility> {
-        match &*pub_ {
+        match pub_ {
             | Visibility::Public(_) => pub_.borrowed(),
             | Visibility::Inherited => Cow::Owned(parse_quote!(pub(super))),
             | Visibility::Restricted(VisRestricted { path, .. }) => {
-                match path.get_ident().map(ToString::to_string).as_deref() {
-                    | Some("crate") => pub_.borrowed(),
-                    | _ if path.leading_colon.is_some() => pub_.borrowed(),
-                    | Some("self") => Cow::Owned(parse_quote!(
-                        pub(super)
-                    )),
+                if path.leading_colon.is_some() {
+                    return pub_.borrowed();
+                }
+                let first = &path.segments[0].ident;
+                match &*first.to_string() {
+                    // Absolute.
+                    | "crate" => pub_.borrowed(),
+                    // `self` is the parent module of the helper one: `super`.
+                    | "self" => {
+                        let mut path = path.clone();
+                        path.segments[0].ident = Ident::new("super", first.span());
+                        Cow::Owned(parse_quote!(
+                            pub(in #path)
+                        ))
+                    },
+                    // `super` and any other relative path.
                     | _ => Cow::Owned(parse_quote!(
                         pub(in super :: #path)
                     )),
@@ -164,11 +253,95 @@ fn drop_with_owned_fields_impl(
         }
     }
     let pub_super = super_of(pub_);
+    // A `#[non_exhaustive]` `Foo` is not to be constructible outside of its crate, so the
+    // `FooFields -> Foo` conversions get capped likewise (but for serde's `from = …`, which
+    // needs the `From` impl).
+    let non_exhaustive = attrs.iter().find(|attr| attr.path().is_ident("non_exhaustive"));
+    let from_fields = non_exhaustive.is_none() || derives::is_derived(attrs, "Deserialize");
+    // The inherent `const fn into()` on `FooFields`: with the visibility of `Foo`, except for
+    // staying private (to the helper module) when `Foo` is, lest it shadow `Into::into()` within
+    // the module of `Foo`.
+    let pub_into = match pub_ {
+        | Visibility::Inherited => pub_.borrowed(),
+        | Visibility::Restricted(VisRestricted { path, .. }) if path.is_ident("self") => {
+            pub_.borrowed()
+        },
+        | Visibility::Public(_) if non_exhaustive.is_some() => Cow::Owned(parse_quote!(
+            pub(crate)
+        )),
+        | _ => super_of(pub_),
+    };
+    let each_field_name = &fields.members().collect::<Vec<_>>();
+    let each_field_name_str = &each_field_name.iter().map(|member| match member {
+        | Member::Named(ident) => ident.unraw().to_string(),
+        | Member::Unnamed(idx) => idx.index.to_string(),
+    }).collect::<Vec<_>>();
+    let each_field_cfgs = &fields.iter().map(|f| utils::cfg_attrs(&f.attrs)).collect::<Vec<_>>();
+    let FIELD_NAMES = if each_field_cfgs.iter().all(Vec::is_empty) {
+        quote!(&[
+            #(#each_field_name_str),*
+        ])
+    } else {
+        // `#[cfg]`s are not allowed on array elements: filter the names in a `const` context.
+        let each_field_is_enabled = each_field_cfgs.iter().map(|cfgs| {
+            let each_predicate = cfgs.iter().map(|cfg| match &cfg.meta {
+                | Meta::List(MetaList { tokens, .. }) => tokens,
+                | _ => unreachable!(),
+            }).collect::<Vec<_>>();
+            match &each_predicate[..] {
+                | [] => quote!(true),
+                | [predicate] => quote!(::core::cfg!(#predicate)),
+                | _ => quote!(::core::cfg!(all(#(#each_predicate),*))),
+            }
+        });
+        quote!({
+            const EACH_FIELD: &[(&str, bool)] = &[
+                #((#each_field_name_str, #each_field_is_enabled)),*
+            ];
+            const LEN: usize = {
+                let (mut len, mut i) = (0, 0);
+                while i < EACH_FIELD.len() {
+                    len += EACH_FIELD[i].1 as usize;
+                    i += 1;
+                }
+                len
+            };
+            const ENABLED: [&str; LEN] = {
+                let (mut ret, mut len, mut i) = ([""; LEN], 0, 0);
+                while i < EACH_FIELD.len() {
+                    if EACH_FIELD[i].1 {
+                        ret[len] = EACH_FIELD[i].0;
+                        len += 1;
+                    }
+                    i += 1;
+                }
+                ret
+            };
+            &ENABLED
+        })
+    };
+
+    let maybe_accessors = accessors::accessors(
+        fields,
+        &each_field_args,
+        args.accessors.as_ref(),
+        &super_of(args.accessors.as_ref().and_then(|it| it.pub_.as_ref()).unwrap_or(pub_)),
+    )?;
+
+    let maybe_delegate_impls = delegate::delegate_impls(
+        args.delegate.as_ref(),
+        StructName,
+        generics,
+        krate,
+        cfgs,
+    )?;
+
     let fields = fields.clone().also(|fields| {
         fields.iter_mut().for_each(|Field { vis: pub_, .. }| {
             *pub_ = super_of(pub_).into_owned();
         });
     });
+    let drop_order = drop_order::drop_order(&fields, args.drop_order.as_ref(), &each_field_args)?;
 
     let pub_capped_at_crate = match &*pub_super {
         | Visibility::Public(_) => Cow::Owned(parse_quote!(
@@ -178,8 +351,125 @@ fn drop_with_owned_fields_impl(
     };
     let (IntroGenerics @ _, FwdGenerics @ _, where_clauses) = generics.split_for_impl();
 
+    // Which type parameters of `Self` the drop logic may let dangle: none of them (`Self`), by
+    // default.
+    let mut NoDangle @ _ = quote!(Self);
+    let mut maybe_eyepatch_impl = quote!();
+    if let Some(may_dangle) = &args.may_dangle {
+        let unsafe_ = &may_dangle.unsafe_;
+        let NoDangleParams @ _ = dropck_eyepatch::non_dangling_params(generics, may_dangle)?;
+        NoDangle = quote!((#(#NoDangleParams ,)*));
+        maybe_eyepatch_impl = quote!(
+            #[automatically_derived]
+            #unsafe_
+            impl #IntroGenerics
+                #ඞ::Eyepatch<#NoDangle>
+            for
+                #StructName #FwdGenerics
+            #where_clauses
+            {}
+        );
+    }
+
+    let maybe_record_custom_drop = args.track_drops.map(|_| quote!(
+        #ඞ::record_custom_drop::<Self>();
+    ));
+
+    // Only the `impl Drop` sugar moves the fields left over into the drop-ordered `struct`.
+    let maybe_assert_drop_sugar = drop_order.is_some().then(|| quote!(
+        #ඞ::assert_drop_sugar::<Self>();
+    ));
+
+    let StructName_str = &StructName.to_string();
+    let mut maybe_enter_drop_span = quote!();
+    let mut maybe_exit_drop_span = quote!();
+    if let Some(args::Trace { trace, level }) = &args.trace {
+        if cfg!(feature = "tracing").not() {
+            return Err(Error::new(trace.span, "\
+                support for this arg requires enabling the `tracing` Cargo \
+                feature, like so:\n    \
+                # Cargo.toml:\n\n    \
+                [dependencies]\n    \
+                # ...\n    \
+                drop-with-owned-fields.version = \"x.y.z\"\n    \
+                drop-with-owned-fields.features = [\"tracing\"]\
+            "));
+        }
+        maybe_enter_drop_span = quote!(
+            let traced_drop = #ඞ::TracedDrop::enter(
+                #ඞ::tracing::span!(
+                    #ඞ::tracing::Level::#level,
+                    #StructName_str,
+                    kind = #ඞ::drop_kind(),
+                )
+            );
+        );
+        maybe_exit_drop_span = quote!(
+            traced_drop.completed();
+        );
+    }
+
+    let call_drop_with_owned_fields = match &args.on_panic {
+        | None | Some(args::OnPanic::Propagate) => quote!(
+            <Self as #krate::DropWithOwnedFields>::drop(fields);
+        ),
+        | Some(args::OnPanic::Abort) => quote!(
+            let abort_on_unwind = #ඞ::AbortOnUnwind(#StructName_str);
+            <Self as #krate::DropWithOwnedFields>::drop(fields);
+            abort_on_unwind.defuse();
+        ),
+        | Some(args::OnPanic::Catch(catch)) => {
+            if cfg!(feature = "std").not() {
+                return Err(Error::new_spanned(catch, "\
+                    support for this policy requires enabling the `std` Cargo \
+                    feature, like so:\n    \
+                    # Cargo.toml:\n\n    \
+                    [dependencies]\n    \
+                    # ...\n    \
+                    drop-with-owned-fields.version = \"x.y.z\"\n    \
+                    drop-with-owned-fields.features = [\"std\"]\
+                "));
+            }
+            quote!(
+                #ඞ::catch_drop_panic(#StructName_str, || {
+                    <Self as #krate::DropWithOwnedFields>::drop(fields);
+                });
+            )
+        },
+    };
+
+    let mut Armed @ _ = quote!(());
+    let mut maybe_disarm_fns = quote!();
+    if args.disarmable.is_some() {
+        Armed = quote!(bool);
+        maybe_disarm_fns = quote!(
+            /// Disables the `DropWithOwnedFields` logic of this value: when dropped, its fields
+            /// shall then merely be dropped, structurally.
+            #[inline]
+            #pub_capped_at_crate
+            fn disarm(&mut self) {
+                #ඞ::ManuallyDropFields::set_armed(&mut self.manually_drop_fields, false);
+            }
+
+            /// Undoes a previous `.disarm()`.
+            #[inline]
+            #pub_capped_at_crate
+            fn rearm(&mut self) {
+                #ඞ::ManuallyDropFields::set_armed(&mut self.manually_drop_fields, true);
+            }
+
+            /// Whether the `DropWithOwnedFields` logic of this value is to run upon drop.
+            #[inline]
+            #pub_capped_at_crate
+            const
+            fn is_armed(&self) -> bool {
+                #ඞ::ManuallyDropFields::is_armed(&self.manually_drop_fields)
+            }
+        );
+    }
+
     let struct_name_helper_module = &format_ident!(
-        "_{StructName}ඞdrop_with_owned_fields"
+        "_{StructName}{INFIX}drop_with_owned_fields"
     );
 
     let fields_struct_span;
@@ -191,6 +481,7 @@ fn drop_with_owned_fields_impl(
             name: StructNameFields @ _,
         }) => {
             maybe_re_export = quote!(
+                #(#cfgs)*
                 #pub_ use #struct_name_helper_module::#StructNameFields;
             );
             fields_struct_span = struct_.span();
@@ -198,158 +489,320 @@ fn drop_with_owned_fields_impl(
         },
         Either::Right(infer) => {
             fields_struct_span = infer.span_location();
-            &format_ident!("{StructName}ඞFields", span=fields_struct_span)
+            &format_ident!("{StructName}{FIELDS_SUFFIX}", span=fields_struct_span)
         },
     };
+    let maybe_as_ref_impls = args.as_ref.is_some().then(|| quote!(
+        #[automatically_derived]
+        impl #IntroGenerics
+            ::core::convert::AsRef<#StructNameFields #FwdGenerics>
+        for
+            #StructName #FwdGenerics
+        #where_clauses
+        {
+            #[inline]
+            fn as_ref(&self) -> &#StructNameFields #FwdGenerics {
+                &*self.manually_drop_fields
+            }
+        }
+        #[automatically_derived]
+        impl #IntroGenerics
+            ::core::convert::AsMut<#StructNameFields #FwdGenerics>
+        for
+            #StructName #FwdGenerics
+        #where_clauses
+        {
+            #[inline]
+            fn as_mut(&mut self) -> &mut #StructNameFields #FwdGenerics {
+                &mut *self.manually_drop_fields
+            }
+        }
+    ));
+    // The `Eq`, `Ord`, and `Hash` impls of `Foo`, if any, are then those of `FooFields`, forwarded
+    // (see `derives.rs`), as per the contract of `Borrow`.
+    let maybe_borrow_impls = args.borrow.is_some().then(|| quote!(
+        #[automatically_derived]
+        impl #IntroGenerics
+            ::core::borrow::Borrow<#StructNameFields #FwdGenerics>
+        for
+            #StructName #FwdGenerics
+        #where_clauses
+        {
+            #[inline]
+            fn borrow(&self) -> &#StructNameFields #FwdGenerics {
+                &*self.manually_drop_fields
+            }
+        }
+        #[automatically_derived]
+        impl #IntroGenerics
+            ::core::borrow::BorrowMut<#StructNameFields #FwdGenerics>
+        for
+            #StructName #FwdGenerics
+        #where_clauses
+        {
+            #[inline]
+            fn borrow_mut(&mut self) -> &mut #StructNameFields #FwdGenerics {
+                &mut *self.manually_drop_fields
+            }
+        }
+    ));
+
+    let maybe_from_impl = from_fields.then(|| quote!(
+        #[automatically_derived]
+        impl #IntroGenerics
+            ::core::convert::From<
+                #StructNameFields #FwdGenerics,
+            >
+        for
+            #StructName #FwdGenerics
+        #where_clauses
+        {
+            #[inline]
+            fn from(this: #StructNameFields #FwdGenerics)
+              -> Self
+            {
+                this.into()
+            }
+        }
+    ));
+
     let struct_fields_def = quote_spanned!(fields_struct_span=>
         #(#attrs)*
         #pub_super
-        struct #StructNameFields #IntroGenerics
+        struct #StructNameFields #generics
         #where_clauses
         #fields
         #semi_token
     );
 
+    let maybe_drop_ordered_struct_def;
+    let DropOrdered @ _;
+    let drop_ordered_fields;
+    match (&drop_order, &fields) {
+        | (Some(order), Fields::Named(FieldsNamed { named, .. })) => {
+            // Hidden, so not subject to the naming lints of the user's code.
+            let StructNameDropOrdered @ _ = &format_ident!(
+                "{StructName}{DROP_ORDERED_SUFFIX}",
+                span = Span::mixed_site().located_at(fields_struct_span),
+            );
+            let each_field_cfgs = named.iter().map(|f| utils::cfg_attrs(&f.attrs)).collect::<Vec<_>>();
+            let each_field = named.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+            let each_ordered_field_def = order.iter().map(|&i| {
+                let Field { vis: pub_, ident, ty: FieldTy @ _, .. } = &named[i];
+                let cfgs = &each_field_cfgs[i];
+                quote!(#(#cfgs)* #pub_ #ident: #FieldTy)
+            });
+            maybe_drop_ordered_struct_def = quote_spanned!(fields_struct_span=>
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                #pub_super
+                struct #StructNameDropOrdered #generics
+                #where_clauses
+                {
+                    #(#each_ordered_field_def),*
+                }
+            );
+            DropOrdered = quote!(#StructNameDropOrdered #FwdGenerics);
+            drop_ordered_fields = quote!(
+                let #StructNameFields {
+                    #( #(#each_field_cfgs)* #each_field, )*
+                } = fields;
+                #StructNameDropOrdered {
+                    #( #(#each_field_cfgs)* #each_field, )*
+                }
+            );
+        },
+        | _ => {
+            maybe_drop_ordered_struct_def = quote!();
+            DropOrdered = quote!(#StructNameFields #FwdGenerics);
+            drop_ordered_fields = quote!(fields);
+        },
+    }
+
     let other_derives_and_attrs_hack =
         derives::best_effort_compat_with_other_derives_and_attrs(
             &input,
             StructNameFields,
+            struct_name_helper_module,
+            krate,
+            cfgs,
+            args.borrow.is_some(),
         )?
     ;
 
-    Ok(quote!(
+    let ret = quote!(
         #other_derives_and_attrs_hack
 
+        #(#cfgs)*
         #[doc(inline)]
-        #(#docs)*
         #pub_ use #struct_name_helper_module::#StructName;
 
         #maybe_re_export
 
+        #maybe_delegate_impls
+
+        #(#cfgs)*
         mod #struct_name_helper_module {
             use super::*;
 
             #struct_fields_def
 
+            #maybe_drop_ordered_struct_def
+
+            #(#docs)*
+            #non_exhaustive
             #[repr(transparent)]
             #pub_super
-            struct #StructName #IntroGenerics
+            struct #StructName #generics
             #where_clauses
             {
-                manually_drop_fields:
-                    ::core::mem::ManuallyDrop<
-                        ::drop_with_owned_fields::DestructuredFieldsOf<Self>,
-                    >
-                ,
+                manually_drop_fields: #ඞ::ManuallyDropFields<Self, #Armed, #NoDangle>,
             }
 
+            #[automatically_derived]
             impl #IntroGenerics
-                ::core::ops::Drop
+                #ඞ::DropFieldsHook
             for
                 #StructName #FwdGenerics
+            #where_clauses
             {
                 #[inline]
-                fn drop(&mut self) {
-                    <Self as ::drop_with_owned_fields::DropWithOwnedFields>::drop(
-                        unsafe {
-                            ::core::mem::ManuallyDrop::take(&mut self.manually_drop_fields)
-                        }
-                    )
+                fn drop_fields(fields: Self::Fields) {
+                    #maybe_assert_drop_sugar
+                    #maybe_record_custom_drop
+                    #maybe_enter_drop_span
+                    #call_drop_with_owned_fields
+                    #maybe_exit_drop_span
                 }
             }
 
+            #maybe_eyepatch_impl
+
+            #[automatically_derived]
             impl #IntroGenerics
-                ::drop_with_owned_fields::ඞ::drop_with_owned_fields_annotation
+                #ඞ::drop_with_owned_fields_annotation
             for
                 #StructName #FwdGenerics
             #where_clauses
             {}
 
+            #[automatically_derived]
             impl #IntroGenerics
-                ::drop_with_owned_fields::DestructureFields
+                #krate::DestructureFields
             for
                 #StructName #FwdGenerics
             #where_clauses
             {
                 type Fields = #StructNameFields #FwdGenerics;
-            }
 
-            impl #IntroGenerics
-                ::core::convert::From<
-                    #StructNameFields #FwdGenerics,
-                >
-            for
-                #StructName #FwdGenerics
-            #where_clauses
-            {
+                const FIELD_NAMES: &'static [&'static str] = #FIELD_NAMES;
+
                 #[inline]
-                fn from(this: #StructNameFields #FwdGenerics)
-                  -> Self
+                fn for_each_field_ref(
+                    &self,
+                    visitor: &mut impl #krate::VisitField,
+                )
                 {
-                    this.into()
+                    #(
+                        #(#each_field_cfgs)*
+                        visitor.visit_field(
+                            #each_field_name_str,
+                            &self.manually_drop_fields.#each_field_name,
+                        );
+                    )*
+                }
+
+                #[inline]
+                fn for_each_field_mut(
+                    &mut self,
+                    visitor: &mut impl #krate::VisitFieldMut,
+                )
+                {
+                    #(
+                        #(#each_field_cfgs)*
+                        visitor.visit_field_mut(
+                            #each_field_name_str,
+                            &mut self.manually_drop_fields.#each_field_name,
+                        );
+                    )*
+                }
+
+                #[inline]
+                fn __fields_ref(&self) -> &Self::Fields {
+                    &*self.manually_drop_fields
+                }
+
+                #[inline]
+                fn __fields_mut(&mut self) -> &mut Self::Fields {
+                    &mut *self.manually_drop_fields
+                }
+
+                type __DropOrdered = #DropOrdered;
+
+                #[inline]
+                fn __drop_ordered(fields: Self::Fields) -> Self::__DropOrdered {
+                    #drop_ordered_fields
                 }
             }
 
+            #maybe_from_impl
+
             impl #IntroGenerics #StructNameFields #FwdGenerics
             #where_clauses
             {
+                /// Wraps these fields back into the type with the `DropWithOwnedFields` logic.
                 #[inline]
-                #pub_
+                #[must_use]
+                #pub_into
                 const
                 fn into(self) -> #StructName #FwdGenerics {
                     #StructName {
-                        manually_drop_fields: ::core::mem::ManuallyDrop::new(
-                            self,
-                        ),
+                        manually_drop_fields: #ඞ::ManuallyDropFields::new(self),
                     }
                 }
             }
 
-            impl #IntroGenerics #StructName #FwdGenerics {
+            impl #IntroGenerics #StructName #FwdGenerics
+            #where_clauses
+            {
                 #[inline]
                 #pub_capped_at_crate
-                const
                 fn destructure_fields_disabling_impl_drop(self: #StructName #FwdGenerics)
                   -> #StructNameFields #FwdGenerics
                 {
-                    // Defuse extra `Drop` glue of `Self`.
-                    let this = ::core::mem::ManuallyDrop::new(self);
-                    unsafe {
-                        /* not `const`:
-                        ::core::mem::ManuallyDrop::take(
-                            &mut this.manually_drop_fields,
-                        )
-                        // not available before `1.83.0`
-                        ::core::mem::transmute_copy(&this)
-                        // */
-                        ::core::mem::ManuallyDrop::into_inner(
-                            ::drop_with_owned_fields::ඞ::ConstTransmuteUnchecked::<
-                                #StructName #FwdGenerics,
-                                #StructNameFields #FwdGenerics,
-                            >
-                            {
-                                src: this,
-                            }
-                            .dst
-                        )
-                    }
+                    let #StructName { manually_drop_fields } = self;
+                    #ඞ::ManuallyDropFields::into_inner(manually_drop_fields)
                 }
+
+                #maybe_disarm_fns
+            }
+
+            impl #IntroGenerics #StructName #FwdGenerics
+            #where_clauses
+            {
+                #maybe_accessors
             }
 
+            #maybe_as_ref_impls
+
+            #maybe_borrow_impls
+
             // if no `deref=false`
+            #[automatically_derived]
             impl #IntroGenerics
                 ::core::ops::Deref
             for
                 #StructName #FwdGenerics
             #where_clauses
             {
-                type Target = ::drop_with_owned_fields::DestructuredFieldsOf<Self>;
+                type Target = #krate::DestructuredFieldsOf<Self>;
 
                 #[inline]
                 fn deref(&self) -> &Self::Target {
                     &*self.manually_drop_fields
                 }
             }
+            #[automatically_derived]
             impl #IntroGenerics
                 ::core::ops::DerefMut
             for
@@ -362,5 +815,9 @@ fn drop_with_owned_fields_impl(
                 }
             }
         }
-    ))
+    );
+    if let Some(debug) = &args.debug {
+        debug::dump(StructName, &ret, debug.span)?;
+    }
+    Ok(ret)
 }
@@ -0,0 +1,86 @@
+use super::*;
+
+/// The inherent `fn field(&self)`, `fn field_mut(&mut self)`, and `fn set_field(&mut self, …)`
+/// accessors on `Foo`, as requested by the `accessors` arg (all of them, for every field), and by
+/// the `#[drop_with_owned_fields(get, get_mut, set)]` field markers (which take precedence).
+///
+/// `pub_` is the visibility of the accessors, already adjusted for the helper module.
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn accessors(
+    fields: &Fields,
+    each_field_args: &[args::FieldArgs],
+    accessors: Option<&args::Accessors>,
+    pub_: &Visibility,
+) -> Result<TokenStream2>
+{
+    let is_marked = |it: &args::FieldArgs| {
+        it.get.is_some() || it.get_mut.is_some() || it.set.is_some()
+    };
+    let span = match (accessors, each_field_args.iter().find(|it| is_marked(it))) {
+        | (Some(args::Accessors { accessors, .. }), _) => accessors.span,
+        | (None, Some(args::FieldArgs { get, get_mut, set, .. })) => {
+            get.map(|it| it.span)
+                .or(get_mut.map(|it| it.span))
+                .or(set.map(|it| it.span))
+                .unwrap()
+        },
+        | (None, None) => return Ok(quote!()),
+    };
+    let Fields::Named(FieldsNamed { named, .. }) = fields else {
+        return Err(Error::new(span, "accessors require named fields"));
+    };
+
+    let mut ret = quote!();
+    let each_field = named.iter().zip(each_field_args);
+    for (Field { attrs, ident, ty: FieldTy @ _, .. }, field_args) in each_field {
+        let field = ident.as_ref().unwrap();
+        let cfgs = utils::cfg_attrs(attrs);
+        let unraw = field.unraw();
+        let (get, get_mut, set) = if is_marked(field_args) {
+            (field_args.get.is_some(), field_args.get_mut.is_some(), field_args.set.is_some())
+        } else {
+            let all = accessors.is_some();
+            (all, all, all)
+        };
+        if get {
+            let doc = format!(" Returns a reference to the `{unraw}` field.");
+            ret.extend(quote!(
+                #(#cfgs)*
+                #[doc = #doc]
+                #[inline]
+                #pub_
+                fn #field(&self) -> &#FieldTy {
+                    &self.manually_drop_fields.#field
+                }
+            ));
+        }
+        if get_mut {
+            let doc = format!(" Returns a mutable reference to the `{unraw}` field.");
+            let field_mut = format_ident!("{unraw}_mut", span = field.span());
+            ret.extend(quote!(
+                #(#cfgs)*
+                #[doc = #doc]
+                #[inline]
+                #pub_
+                fn #field_mut(&mut self) -> &mut #FieldTy {
+                    &mut self.manually_drop_fields.#field
+                }
+            ));
+        }
+        if set {
+            let doc = format!(" Sets the `{unraw}` field, returning its previous value.");
+            let set_field = format_ident!("set_{unraw}", span = field.span());
+            ret.extend(quote!(
+                #(#cfgs)*
+                #[doc = #doc]
+                #[inline]
+                #pub_
+                fn #set_field(&mut self, value: #FieldTy) -> #FieldTy {
+                    ::core::mem::replace(&mut self.manually_drop_fields.#field, value)
+                }
+            ));
+        }
+    }
+    Ok(ret)
+}
@@ -1,13 +1,41 @@
 use super::*;
 
 mod kw {
-    // ...
+    ::syn::custom_keyword!(accessors);
+    ::syn::custom_keyword!(as_ref);
+    ::syn::custom_keyword!(borrow);
+    ::syn::custom_keyword!(debug);
+    ::syn::custom_keyword!(delegate);
+    ::syn::custom_keyword!(disarmable);
+    ::syn::custom_keyword!(drop_last);
+    ::syn::custom_keyword!(drop_order);
+    ::syn::custom_keyword!(get);
+    ::syn::custom_keyword!(get_mut);
+    ::syn::custom_keyword!(level);
+    ::syn::custom_keyword!(may_dangle);
+    ::syn::custom_keyword!(on_panic);
+    ::syn::custom_keyword!(reverse_drop_order);
+    ::syn::custom_keyword!(set);
+    ::syn::custom_keyword!(trace);
+    ::syn::custom_keyword!(track_drops);
 }
 
 pub(crate)
 struct Args {
     pub(crate) _as: Token![as],
     pub(crate) maybe_rename: Either<RenameOfDestructuredFieldsType, Token![_]>,
+    pub(crate) may_dangle: Option<MayDangle>,
+    pub(crate) drop_order: Option<DropOrder>,
+    pub(crate) disarmable: Option<kw::disarmable>,
+    pub(crate) track_drops: Option<kw::track_drops>,
+    pub(crate) trace: Option<Trace>,
+    pub(crate) on_panic: Option<OnPanic>,
+    pub(crate) accessors: Option<Accessors>,
+    pub(crate) debug: Option<kw::debug>,
+    pub(crate) delegate: Option<Delegate>,
+    pub(crate) as_ref: Option<kw::as_ref>,
+    pub(crate) borrow: Option<kw::borrow>,
+    pub(crate) krate: Option<Crate>,
 }
 
 impl Parse for Args {
@@ -27,14 +55,118 @@ impl Parse for Args {
                 }
             };
             let _: Option<Token![,]> = input.parse()?;
+            let mut may_dangle = None;
+            let mut drop_order = None;
+            let mut disarmable = None;
+            let mut track_drops = None;
+            let mut trace = None;
+            let mut on_panic = None;
+            let mut accessors = None;
+            let mut debug = None;
+            let mut delegate = None;
+            let mut as_ref = None;
+            let mut borrow = None;
+            let mut krate = None;
             while input.is_empty().not() {
                 let peeker = input.lookahead1();
                 match () {
+                    | _case if peeker.peek(Token![unsafe]) => {
+                        let it: MayDangle = input.parse()?;
+                        set_once(&mut may_dangle, it.may_dangle.span, it)?;
+                    },
+                    | _case if peeker.peek(kw::drop_order) => {
+                        let kw: kw::drop_order = input.parse()?;
+                        let fields;
+                        parenthesized!(fields in input);
+                        let fields = Punctuated::parse_terminated(&fields)?;
+                        set_once(&mut drop_order, kw.span, DropOrder::Explicit(kw, fields))?;
+                    },
+                    | _case if peeker.peek(kw::reverse_drop_order) => {
+                        let kw: kw::reverse_drop_order = input.parse()?;
+                        set_once(&mut drop_order, kw.span, DropOrder::Reverse(kw))?;
+                    },
+                    | _case if peeker.peek(kw::disarmable) => {
+                        let kw: kw::disarmable = input.parse()?;
+                        set_once(&mut disarmable, kw.span, kw)?;
+                    },
+                    | _case if peeker.peek(kw::track_drops) => {
+                        let kw: kw::track_drops = input.parse()?;
+                        set_once(&mut track_drops, kw.span, kw)?;
+                    },
+                    | _case if peeker.peek(kw::trace) => {
+                        let it: Trace = input.parse()?;
+                        set_once(&mut trace, it.trace.span, it)?;
+                    },
+                    | _case if peeker.peek(kw::on_panic) => {
+                        let kw: kw::on_panic = input.parse()?;
+                        let _: Token![=] = input.parse()?;
+                        let policy: Ident = input.parse()?;
+                        let it = match &policy.to_string()[..] {
+                            | "abort" => OnPanic::Abort,
+                            | "catch" => OnPanic::Catch(policy),
+                            | "propagate" => OnPanic::Propagate,
+                            | _ => return Err(Error::new_spanned(policy, "\
+                                expected `abort`, `catch`, or `propagate`\
+                            ")),
+                        };
+                        set_once(&mut on_panic, kw.span, it)?;
+                    },
+                    | _case if peeker.peek(kw::accessors) => {
+                        let it: Accessors = input.parse()?;
+                        set_once(&mut accessors, it.accessors.span, it)?;
+                    },
+                    | _case if peeker.peek(kw::debug) => {
+                        let kw: kw::debug = input.parse()?;
+                        set_once(&mut debug, kw.span, kw)?;
+                    },
+                    | _case if peeker.peek(kw::delegate) => {
+                        let kw: kw::delegate = input.parse()?;
+                        let traits;
+                        parenthesized!(traits in input);
+                        let traits = Punctuated::parse_terminated(&traits)?;
+                        set_once(&mut delegate, kw.span, Delegate { _delegate: kw, traits })?;
+                    },
+                    | _case if peeker.peek(kw::as_ref) => {
+                        let kw: kw::as_ref = input.parse()?;
+                        set_once(&mut as_ref, kw.span, kw)?;
+                    },
+                    | _case if peeker.peek(kw::borrow) => {
+                        let kw: kw::borrow = input.parse()?;
+                        set_once(&mut borrow, kw.span, kw)?;
+                    },
+                    | _case if peeker.peek(Token![crate]) => {
+                        let it: Crate = input.parse()?;
+                        set_once(&mut krate, it.crate_.span, it)?;
+                    },
+                    | _case if peeker.peek(kw::may_dangle) => {
+                        let kw: kw::may_dangle = input.parse()?;
+                        return Err(Error::new_spanned(kw, "\
+                            `may_dangle` is `unsafe` to use: \
+                            write `unsafe(may_dangle(…))` instead\
+                        "));
+                    },
                     | _default => return Err(peeker.error()),
                 }
-                // let _: Option<Token![,]> = input.parse()?;
+                if input.is_empty().not() {
+                    let _: Token![,] = input.parse()?;
+                }
             }
-            Ok(Self { _as: as_, maybe_rename })
+            Ok(Self {
+                _as: as_,
+                maybe_rename,
+                may_dangle,
+                drop_order,
+                disarmable,
+                track_drops,
+                trace,
+                on_panic,
+                accessors,
+                debug,
+                delegate,
+                as_ref,
+                borrow,
+                krate,
+            })
         }().map_err(|mut err| {
             err.combine(Error::new_spanned(
                 &err.to_compile_error(),
@@ -45,6 +177,47 @@ Usage:
         as _
         // or:
         as $( $pub:vis )? struct $FooFields:ident,
+
+        // Optional, either:
+        drop_order( $($field:ident),* ),
+        // or:
+        reverse_drop_order,
+
+        // Optional:
+        disarmable,
+
+        // Optional (no-op unless the `testing` Cargo feature is enabled):
+        track_drops,
+
+        // Optional (`tracing` Cargo feature):
+        trace $( (level = \"trace\" | \"debug\" | \"info\" | \"warn\" | \"error\") )?,
+
+        // Optional (`catch` requires the `std` Cargo feature):
+        on_panic = abort | catch | propagate,
+
+        // Optional, nightly-only (`dropck-eyepatch` Cargo feature):
+        unsafe(may_dangle( $($T:ident),* )),
+
+        // Optional (defaults to the visibility of the `struct`):
+        accessors $( ($pub:vis) )?,
+
+        // Optional: dumps the expansion to `stderr`, or, when that env var is set, to
+        // `$DROP_WITH_OWNED_FIELDS_DEBUG_DIR/<crate_name>-$Foo-<hash>.rs`.
+        debug,
+
+        // Optional: forwards these trait impls to those of `DestructuredFieldsOf<Self>`
+        // (well-known ones, such as `Display` or `AsRef<T>`, or `#[delegatable]` ones).
+        delegate( $($Trait:path),* ),
+
+        // Optional: `As{Ref,Mut}<$FooFields>` impls.
+        as_ref,
+
+        // Optional: `Borrow{,Mut}<$FooFields>` impls.
+        borrow,
+
+        // Optional (defaults to `::drop_with_owned_fields`): the path through which this crate
+        // is to be accessed, for when the attribute is used through a re-export.
+        crate = $path,
     )]
     ...\
                 ",
@@ -54,6 +227,14 @@ Usage:
     }
 }
 
+fn set_once<T>(slot: &mut Option<T>, span: Span, value: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new(span, "duplicate arg"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
 pub(crate)
 struct RenameOfDestructuredFieldsType {
     pub(crate) pub_: Visibility,
@@ -70,3 +251,215 @@ impl Parse for RenameOfDestructuredFieldsType {
         })
     }
 }
+
+/// `unsafe(may_dangle(T, …))`
+pub(crate)
+struct MayDangle {
+    pub(crate) unsafe_: Token![unsafe],
+    pub(crate) may_dangle: kw::may_dangle,
+    pub(crate) params: Punctuated<Ident, Token![,]>,
+}
+
+impl Parse for MayDangle {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let unsafe_ = input.parse()?;
+        let contents;
+        parenthesized!(contents in input);
+        let may_dangle = contents.parse()?;
+        let params;
+        parenthesized!(params in contents);
+        let _: Option<Token![,]> = contents.parse()?;
+        let _: parse::Nothing = contents.parse()?;
+        Ok(Self {
+            unsafe_,
+            may_dangle,
+            params: Punctuated::parse_terminated_with(&params, |input| {
+                if input.peek(Lifetime) {
+                    let lifetime: Lifetime = input.parse()?;
+                    return Err(Error::new_spanned(lifetime, "\
+                        only type parameters are supported: a `#[may_dangle]` lifetime could \
+                        otherwise be used to access dangling borrows from within the \
+                        `DropWithOwnedFields` logic\
+                    "));
+                }
+                input.parse()
+            })?,
+        })
+    }
+}
+
+/// `drop_order(b, a, c)` or `reverse_drop_order`.
+pub(crate)
+enum DropOrder {
+    Explicit(kw::drop_order, Punctuated<Ident, Token![,]>),
+    Reverse(kw::reverse_drop_order),
+}
+
+/// The `#[drop_with_owned_fields(…)]` annotations on a field.
+#[derive(Default)]
+pub(crate)
+struct FieldArgs {
+    pub(crate) drop_last: Option<kw::drop_last>,
+    pub(crate) get: Option<kw::get>,
+    pub(crate) get_mut: Option<kw::get_mut>,
+    pub(crate) set: Option<kw::set>,
+}
+
+impl FieldArgs {
+    /// Parses, and strips, the `#[drop_with_owned_fields(…)]` attributes among the given ones.
+    pub(crate)
+    fn extract_from(attrs: &mut Vec<Attribute>) -> Result<Self> {
+        let mut ret = Self::default();
+        let mut errors = None;
+        attrs.retain(|attr| {
+            if attr.path().is_ident("drop_with_owned_fields").not() {
+                return true;
+            }
+            if let Err(err) = attr.parse_args_with(|input: ParseStream<'_>| ret.parse_into(input)) {
+                errors.get_or_insert(err);
+            }
+            false
+        });
+        match errors {
+            | Some(err) => Err(err),
+            | None => Ok(ret),
+        }
+    }
+
+    fn parse_into(&mut self, input: ParseStream<'_>) -> Result<()> {
+        while input.is_empty().not() {
+            let peeker = input.lookahead1();
+            match () {
+                | _case if peeker.peek(kw::drop_last) => {
+                    let kw: kw::drop_last = input.parse()?;
+                    set_once(&mut self.drop_last, kw.span, kw)?;
+                },
+                | _case if peeker.peek(kw::get) => {
+                    let kw: kw::get = input.parse()?;
+                    set_once(&mut self.get, kw.span, kw)?;
+                },
+                | _case if peeker.peek(kw::get_mut) => {
+                    let kw: kw::get_mut = input.parse()?;
+                    set_once(&mut self.get_mut, kw.span, kw)?;
+                },
+                | _case if peeker.peek(kw::set) => {
+                    let kw: kw::set = input.parse()?;
+                    set_once(&mut self.set, kw.span, kw)?;
+                },
+                | _default => return Err(peeker.error()),
+            }
+            if input.is_empty().not() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `on_panic = …`
+pub(crate)
+enum OnPanic {
+    Abort,
+    Catch(Ident),
+    Propagate,
+}
+
+/// `trace` or `trace(level = "…")`.
+pub(crate)
+struct Trace {
+    pub(crate) trace: kw::trace,
+    /// `TRACE`, `DEBUG`, `INFO`, `WARN`, or `ERROR`.
+    pub(crate) level: Ident,
+}
+
+impl Parse for Trace {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let trace: kw::trace = input.parse()?;
+        let mut level = Ident::new("TRACE", trace.span);
+        if input.peek(token::Paren) {
+            let contents;
+            parenthesized!(contents in input);
+            let _: kw::level = contents.parse()?;
+            let _: Token![=] = contents.parse()?;
+            let lit: LitStr = contents.parse()?;
+            let _: Option<Token![,]> = contents.parse()?;
+            let _: parse::Nothing = contents.parse()?;
+            let value = lit.value();
+            if ["trace", "debug", "info", "warn", "error"].contains(&&*value).not() {
+                return Err(Error::new_spanned(lit, "\
+                    expected one of `\"trace\"`, `\"debug\"`, `\"info\"`, `\"warn\"`, \
+                    or `\"error\"`\
+                "));
+            }
+            level = Ident::new(&value.to_uppercase(), lit.span());
+        }
+        Ok(Self { trace, level })
+    }
+}
+
+/// `accessors` or `accessors($pub)`.
+pub(crate)
+struct Accessors {
+    pub(crate) accessors: kw::accessors,
+    pub(crate) pub_: Option<Visibility>,
+}
+
+impl Parse for Accessors {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let accessors = input.parse()?;
+        let mut pub_ = None;
+        if input.peek(token::Paren) {
+            let contents;
+            parenthesized!(contents in input);
+            pub_ = Some(contents.parse()?);
+            let _: parse::Nothing = contents.parse()?;
+        }
+        Ok(Self { accessors, pub_ })
+    }
+}
+
+/// `delegate(Trait, …)`
+pub(crate)
+struct Delegate {
+    pub(crate) _delegate: kw::delegate,
+    pub(crate) traits: Punctuated<Path, Token![,]>,
+}
+
+/// `crate = ::path::to::drop_with_owned_fields`
+pub(crate)
+struct Crate {
+    pub(crate) crate_: Token![crate],
+    pub(crate) path: Path,
+}
+
+impl Parse for Crate {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let crate_ = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        Ok(Self {
+            crate_,
+            path: Path::parse_mod_style(input)?,
+        })
+    }
+}
+
+/// The path to this crate, to be used by every generated path: `::drop_with_owned_fields` unless
+/// overridden by a `crate = …` arg.
+pub(crate)
+fn crate_path(krate: Option<&Crate>) -> TokenStream2 {
+    match krate {
+        | Some(Crate { path, .. }) => path.to_token_stream(),
+        | None => quote!(::drop_with_owned_fields),
+    }
+}
+
+/// The path to the hidden `ඞ` module of this crate, or to its `__private` ASCII alias with the
+/// `ascii-idents` Cargo feature.
+pub(crate)
+fn internals_path(krate: &TokenStream2) -> TokenStream2 {
+    if cfg!(feature = "ascii-idents") {
+        quote!(#krate::__private)
+    } else {
+        quote!(#krate::ඞ)
+    }
+}
@@ -0,0 +1,322 @@
+use super::*;
+use ::proc_macro2::{Delimiter, Spacing};
+
+/// The env var naming a directory into which to write the `debug` expansions, rather than
+/// printing them to `stderr`.
+const DEBUG_DIR_ENV_VAR: &str = "DROP_WITH_OWNED_FIELDS_DEBUG_DIR";
+
+/// Dumps the pretty-printed `expansion` of `#[drop_with_owned_fields(…, debug)]` on `StructName`.
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn dump(
+    StructName @ _: &Ident,
+    expansion: &TokenStream2,
+    debug_span: Span,
+) -> Result<()>
+{
+    let contents = format!(
+        "// `#[drop_with_owned_fields]` expansion of `{StructName}`\n{}",
+        pretty_print(expansion.clone()),
+    );
+    let Some(dir) = ::std::env::var_os(DEBUG_DIR_ENV_VAR) else {
+        ::std::eprintln!("{contents}");
+        return Ok(());
+    };
+    // Proc-macros get to see neither the module path nor (on stable) the location of the
+    // annotated `struct`, so same-named ones are told apart by a hash of their expansion (in
+    // which case identical expansions harmlessly share a file), and by the crate being compiled.
+    let krate = ::std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    // FNV-1a, rather than the `std` hasher (whose algorithm is unspecified, and may change
+    // across Rust versions), for the file names to be stable across builds.
+    let hash = contents.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let path = ::std::path::Path::new(&dir).join(format!("{krate}-{StructName}-{hash:016x}.rs"));
+    ::std::fs::create_dir_all(&dir)
+        .and_then(|()| ::std::fs::write(&path, contents))
+        .map_err(|err| Error::new(debug_span, format_args!(
+            "failed to write `{}` (from `{DEBUG_DIR_ENV_VAR}`): {err}",
+            path.display(),
+        )))
+}
+
+/// A best-effort, but deterministic, pretty-printing of `tokens`: one statement / item / field
+/// per line, with the contents of braces indented.
+fn pretty_print(tokens: TokenStream2) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        indent: 0,
+        at_line_start: true,
+        glue_next: false,
+        prev: None,
+        prev_closes_impl_generics: false,
+    };
+    printer.print(tokens, true);
+    printer.newline();
+    printer.out
+}
+
+struct Printer {
+    out: String,
+    indent: usize,
+    at_line_start: bool,
+    /// Whether the next token is to be printed without a separating space.
+    glue_next: bool,
+    prev: Option<TT>,
+    /// Whether `prev` is the `>` of `impl<…>`, which, unlike that of `<T as Trait>`, is not to be
+    /// glued to a subsequent `::`.
+    prev_closes_impl_generics: bool,
+}
+
+impl Printer {
+    fn print(&mut self, tokens: TokenStream2, is_braced: bool) {
+        let tts = tokens.into_iter().collect::<Vec<_>>();
+        // Whether each currently open `<` is that of an `impl<…>`.
+        let mut angle_brackets = vec![];
+        for (i, tt) in tts.iter().enumerate() {
+            let mut closes_impl_generics = false;
+            match tt {
+                | TT::Punct(p) => match p.as_char() {
+                    | ';' => {
+                        self.glued(";");
+                        self.newline();
+                    },
+                    | ',' => {
+                        self.glued(",");
+                        if is_braced && angle_brackets.is_empty() {
+                            self.newline();
+                        }
+                    },
+                    | '<' => {
+                        let is_impl_generics = matches!(
+                            &self.prev,
+                            Some(TT::Ident(ident)) if ident == "impl",
+                        );
+                        angle_brackets.push(is_impl_generics);
+                        if is_impl_generics
+                        || self.prev_is_path_segment()
+                        || self.prev_is_joint_colon()
+                        {
+                            self.glued("<");
+                        } else {
+                            self.word("<");
+                        }
+                        self.glue_next = true;
+                    },
+                    // Not the `>` of `->` nor `=>`.
+                    | '>' if matches!(
+                        &self.prev,
+                        Some(TT::Punct(p)) if p.spacing() == Spacing::Joint,
+                    ).not() => {
+                        closes_impl_generics = angle_brackets.pop().unwrap_or(false);
+                        self.glued(">");
+                    },
+                    // Unary operators (as far as the expansion is concerned).
+                    | c @ ('&' | '*' | '!') => {
+                        self.word(c);
+                        self.glue_next = true;
+                    },
+                    | '.' => {
+                        self.glued(".");
+                        self.glue_next = true;
+                    },
+                    // `::`
+                    | ':' if p.spacing() == Spacing::Joint || self.prev_is_joint_colon() => {
+                        if self.prev_is_joint_colon() || self.prev_is_path_segment() {
+                            self.glued(":");
+                        } else {
+                            self.word(":");
+                        }
+                        self.glue_next = true;
+                    },
+                    | ':' => self.glued(":"),
+                    | c => {
+                        self.word(c);
+                        self.glue_next = c == '#' || p.spacing() == Spacing::Joint;
+                    },
+                },
+                | TT::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    self.word("{");
+                    if group.stream().is_empty() {
+                        self.glued("}");
+                    } else {
+                        self.indent += 1;
+                        self.newline();
+                        self.print(group.stream(), true);
+                        self.indent -= 1;
+                        self.newline();
+                        self.word("}");
+                    }
+                    let is_followed_by_punct = matches!(
+                        tts.get(i + 1),
+                        Some(TT::Punct(p)) if [',', ';', '.'].contains(&p.as_char())
+                    );
+                    if is_braced && is_followed_by_punct.not() {
+                        self.newline();
+                    }
+                },
+                | TT::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        | Delimiter::Parenthesis => ("(", ")"),
+                        | Delimiter::Bracket => ("[", "]"),
+                        | _ => ("", ""),
+                    };
+                    let is_attr = matches!(&self.prev, Some(TT::Punct(p)) if p.as_char() == '#');
+                    if group.delimiter() == Delimiter::Parenthesis
+                    && (self.prev_is_path_segment() || self.prev_is_ident("pub"))
+                    {
+                        self.glue_next = true;
+                    }
+                    self.word(open);
+                    self.glue_next = true;
+                    self.print(group.stream(), false);
+                    self.glued(close);
+                    if is_attr && is_braced {
+                        self.newline();
+                    }
+                },
+                | TT::Ident(_) | TT::Literal(_) => self.word(tt),
+            }
+            self.prev = Some(tt.clone());
+            self.prev_closes_impl_generics = closes_impl_generics;
+        }
+    }
+
+    fn word(&mut self, s: impl ::core::fmt::Display) {
+        if self.at_line_start {
+            self.out.extend(::core::iter::repeat("    ").take(self.indent));
+        } else if self.glue_next.not() {
+            self.out.push(' ');
+        }
+        self.out += &s.to_string();
+        self.at_line_start = false;
+        self.glue_next = false;
+    }
+
+    fn glued(&mut self, s: &str) {
+        self.glue_next = true;
+        self.word(s);
+    }
+
+    fn newline(&mut self) {
+        if self.at_line_start.not() {
+            self.out.push('\n');
+            self.at_line_start = true;
+        }
+    }
+
+    fn prev_is_joint_colon(&self) -> bool {
+        matches!(
+            &self.prev,
+            Some(TT::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint
+        )
+    }
+
+    fn prev_is_ident(&self, name: &str) -> bool {
+        matches!(&self.prev, Some(TT::Ident(ident)) if ident == name)
+    }
+
+    fn prev_is_path_segment(&self) -> bool {
+        match &self.prev {
+            | Some(TT::Ident(ident)) => [
+                "as", "dyn", "fn", "for", "impl", "in", "let", "mut", "pub", "return", "type",
+                "use", "where",
+            ].iter().any(|kw| ident == kw).not(),
+            | Some(TT::Punct(p)) => p.as_char() == '>' && self.prev_closes_impl_generics.not(),
+            | _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Set this env var to (re)write the snapshot rather than checking against it.
+    const BLESS_ENV_VAR: &str = "DROP_WITH_OWNED_FIELDS_BLESS";
+
+    #[test]
+    fn snapshot() {
+        let expansion = crate::drop_with_owned_fields_impl(
+            quote!(as pub struct LoggerFields),
+            quote!(
+                /// Docs.
+                pub struct Logger<'out, T : ::core::fmt::Display = String>
+                where
+                    T : Clone,
+                {
+                    pub out: &'out mut String,
+                    lines: Vec<T>,
+                }
+            ),
+        ).unwrap();
+        let printed = pretty_print(expansion);
+        let path =
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("snapshots/debug.expanded.rs")
+        ;
+        if ::std::env::var_os(BLESS_ENV_VAR).is_some() {
+            ::std::fs::write(&path, &printed).unwrap();
+        }
+        let snapshot = ::std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            printed == snapshot,
+            "`{}` mismatch (set `{BLESS_ENV_VAR}` to update it); got:\n{printed}",
+            path.display(),
+        );
+    }
+
+    /// `#![forbid(unsafe_code)]` does not fire within the expansion of an external macro, so
+    /// `tests/forbid_unsafe.rs` cannot catch it on its own.
+    #[test]
+    fn no_unsafe() {
+        fn mentions_unsafe(tokens: TokenStream2) -> bool {
+            tokens.into_iter().any(|tt| match tt {
+                | TT::Ident(ident) => ident == "unsafe",
+                | TT::Group(group) => mentions_unsafe(group.stream()),
+                | _ => false,
+            })
+        }
+        let expansion = crate::drop_with_owned_fields_impl(
+            quote!(
+                as pub struct GuardFields,
+                disarmable,
+                track_drops,
+                on_panic = abort,
+                accessors,
+                as_ref,
+                borrow,
+                delegate(::core::fmt::Display),
+            ),
+            quote!(
+                #[derive(Clone, Default, PartialEq)]
+                pub struct Guard<'a, T> {
+                    a: &'a T,
+                    b: T,
+                }
+            ),
+        ).unwrap();
+        assert!(mentions_unsafe(expansion).not());
+    }
+
+    #[test]
+    fn same_named_structs_do_not_collide() {
+        let dir =
+            ::std::env::temp_dir()
+                .join(format!("{DEBUG_DIR_ENV_VAR}-{}", ::std::process::id()))
+        ;
+        // This is the only test touching this env var.
+        ::std::env::set_var(DEBUG_DIR_ENV_VAR, &dir);
+        for fields in [quote!({ a: u8 }), quote!({ b: u16 })] {
+            let expansion = crate::drop_with_owned_fields_impl(
+                quote!(as _),
+                quote!(struct Foo #fields),
+            ).unwrap();
+            dump(&format_ident!("Foo"), &expansion, Span::call_site()).unwrap();
+        }
+        ::std::env::remove_var(DEBUG_DIR_ENV_VAR);
+        let dumped_files = ::std::fs::read_dir(&dir).unwrap().count();
+        ::std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(dumped_files, 2);
+    }
+}
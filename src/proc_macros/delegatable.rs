@@ -0,0 +1,226 @@
+//! `#[delegatable] trait Trait { … }`: defines a `Trait!` `macro_rules!` homonym, which the
+//! `delegate(Trait)` arg of `#[drop_with_owned_fields]` expands to, so as to get an
+//! `impl Trait for Foo` forwarding each item to `DestructuredFieldsOf<Foo>`.
+//!
+//! The `trait` definition is walked with the [`token_walking`] helpers, rather than requiring
+//! `syn/full` to parse it.
+
+use super::*;
+use ::proc_macro2::{Delimiter, Group};
+use token_walking::*;
+
+pub(crate)
+fn handle(
+    args: TokenStream2,
+    input: TokenStream2,
+) -> Result<TokenStream2>
+{
+    let _: parse::Nothing = parse2(args)?;
+    let (pub_, Trait, body) = Parser::parse2(
+        |input: ParseStream<'_>| {
+            let _: Vec<Attribute> = Attribute::parse_outer(input)?;
+            let pub_: Visibility = input.parse()?;
+            if let Some(unsafe_) = input.parse::<Option<Token![unsafe]>>()? {
+                return Err(Error::new_spanned(unsafe_, "`unsafe` traits cannot be delegated"));
+            }
+            let _: Token![trait] = input.parse()?;
+            let Trait: Ident = input.parse()?;
+            if input.peek(Token![<]) {
+                return Err(input.error("generic traits cannot be delegated"));
+            }
+            // Supertraits and `where` clauses: only relevant to the `trait` definition.
+            let mut body = None;
+            while input.is_empty().not() {
+                if let TT::Group(group) = input.parse()? {
+                    if group.delimiter() == Delimiter::Brace {
+                        body = Some(group);
+                    }
+                }
+            }
+            Ok((pub_, Trait, body.unwrap()))
+        },
+        input.clone(),
+    )?;
+
+    let items = body.stream().into_iter().collect::<Vec<TT>>();
+    let mut start = 0;
+    // Skip the inner attributes, if any.
+    while is_punct(items.get(start), '#') && is_punct(items.get(start + 1), '!') {
+        start += 3;
+    }
+    let mut forwarding_items = quote!();
+    while start < items.len() {
+        let item = &items[start .. start + item_len(&items[start ..])];
+        start += item.len();
+        forwarding_items.extend(forwarding_item(item)?);
+    }
+
+    let macro_name = format_ident!("__drop_with_owned_fields_delegate_{Trait}");
+    let maybe_macro_export = matches!(pub_, Visibility::Public(_)).then(|| quote!(
+        #[macro_export]
+    ));
+    Ok(quote!(
+        #input
+
+        #[doc(hidden)]
+        #maybe_macro_export
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {(
+            { $($krate:tt)* }
+            { $($impl_generics:tt)* }
+            { $($Trait:tt)* }
+            { $($Self:tt)* }
+            { $($where_clause:tt)* }
+        ) => (
+            #[automatically_derived]
+            $($impl_generics)* $($Trait)* for $($Self)* $($where_clause)* {
+                #forwarding_items
+            }
+        )}
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        #pub_ use #macro_name as #Trait;
+    ))
+}
+
+/// The `impl` counterpart of a `trait` item, forwarding to the `DestructuredFieldsOf<Self>` one.
+///
+/// Uses the `$($krate)*` and `$($Trait)*` metavariables of the `macro_rules!` definition.
+fn forwarding_item(item: &[TT]) -> Result<TokenStream2> {
+    let Fields = quote!($($krate)*::DestructuredFieldsOf<Self>);
+    // Keep the `#[cfg]`s, only.
+    let mut cfgs = quote!();
+    let mut j = 0;
+    while is_punct(item.get(j), '#') {
+        let is_cfg = matches!(
+            &item[j + 1],
+            TT::Group(attr)
+            if attr.stream().into_iter().next().is_some_and(|tt| is_ident(&tt, "cfg"))
+        );
+        if is_cfg {
+            cfgs.extend(stream(&item[j .. j + 2]));
+        }
+        j += 2;
+    }
+    let item = &item[j ..];
+    match item.first() {
+        // `type Name…;`
+        | Some(type_) if is_ident(type_, "type") => {
+            let Some(TT::Ident(Name)) = item.get(1) else { unreachable!() };
+            if is_punct(item.get(2), '<') {
+                return Err(Error::new_spanned(
+                    stream(item),
+                    "generic associated types cannot be delegated",
+                ));
+            }
+            Ok(quote!(
+                #cfgs
+                type #Name = <#Fields as $($Trait)*>::#Name;
+            ))
+        },
+        // `const NAME: Ty…;`
+        | Some(const_) if is_ident(const_, "const") && fn_keyword(item).is_none() => {
+            let Some(TT::Ident(NAME)) = item.get(1) else { unreachable!() };
+            let is_end = |tt: &TT| is_punct(Some(tt), '=') || is_punct(Some(tt), ';');
+            let end = position_at_depth_0(&item[3 ..], is_end).map_or(item.len(), |end| 3 + end);
+            let Ty = stream(&item[3 .. end]);
+            Ok(quote!(
+                #cfgs
+                const #NAME: #Ty = <#Fields as $($Trait)*>::#NAME;
+            ))
+        },
+        | _ => match fn_keyword(item) {
+            | Some(fn_) => forwarding_fn(cfgs, item, fn_, &Fields),
+            | None => Err(Error::new_spanned(stream(item), "unsupported item")),
+        },
+    }
+}
+
+/// `qualifiers… fn ident<…>(args…) -> Ret where …` followed by either `;` or a default body.
+#[allow(clippy::redundant_pattern)]
+fn forwarding_fn(
+    cfgs: TokenStream2,
+    item: &[TT],
+    fn_: usize,
+    Fields @ _: &TokenStream2,
+) -> Result<TokenStream2>
+{
+    let qualifiers = &item[.. fn_];
+    let TT::Ident(ident) = &item[fn_ + 1] else { unreachable!() };
+    let args_idx = fn_ + 2 + position_at_depth_0(&item[fn_ + 2 ..], |tt| matches!(
+        tt,
+        TT::Group(g) if g.delimiter() == Delimiter::Parenthesis
+    )).unwrap();
+    let TT::Group(args) = &item[args_idx] else { unreachable!() };
+    // Up to the default body, or the `;`.
+    let sig_rest = &item[args_idx + 1 .. item.len() - 1];
+    let has_default = matches!(
+        item.last(),
+        Some(TT::Group(g)) if g.delimiter() == Delimiter::Brace
+    );
+
+    let mut new_args = vec![];
+    let mut call_args = vec![];
+    for (i, arg) in split_at_depth_0_commas(args.stream()).iter().enumerate() {
+        let mut attrs_len = 0;
+        while is_punct(arg.get(attrs_len), '#') {
+            attrs_len += 2;
+        }
+        let arg = &arg[attrs_len ..];
+        if i == 0 && is_receiver(arg) {
+            let self_ = Ident::new("self", Span::mixed_site());
+            // `&self`, `&'a mut self`, …
+            let receiver = arg.iter().map(|tt| match tt {
+                | TT::Ident(ident) if ident == "self" => TT::Ident(self_.clone()),
+                | _ => tt.clone(),
+            }).collect::<TokenStream2>();
+            let by_ref = is_punct(arg.first(), '&');
+            let by_mut = by_ref && arg.iter().any(|tt| is_ident(tt, "mut"));
+            if by_ref.not() || arg.iter().any(|tt| is_punct(Some(tt), ':')) {
+                if has_default {
+                    // Keep the default implementation.
+                    return Ok(quote!());
+                }
+                return Err(Error::new_spanned(stream(arg), "\
+                    only `&self` and `&mut self` receivers can be delegated (or methods with a \
+                    default implementation)\
+                "));
+            }
+            new_args.push(receiver);
+            call_args.push(if by_mut {
+                quote!(&mut **#self_)
+            } else {
+                quote!(&**#self_)
+            });
+            continue;
+        }
+        let colon = position_at_depth_0(arg, |tt| is_punct(Some(tt), ':')).unwrap();
+        let Ty = stream(&arg[colon + 1 ..]);
+        let arg_name = format_ident!("arg_{i}", span = Span::mixed_site());
+        new_args.push(quote!(#arg_name: #Ty));
+        call_args.push(quote!(#arg_name));
+    }
+    let mut new_args = Group::new(Delimiter::Parenthesis, quote!(#(#new_args),*));
+    new_args.set_span(args.span());
+
+    let generics = stream(&item[fn_ + 2 .. args_idx]);
+    let sig_rest = stream(sig_rest);
+    let mut call = quote!(
+        <#Fields as $($Trait)*>::#ident(#(#call_args),*)
+    );
+    if qualifiers.iter().any(|tt| is_ident(tt, "async")) {
+        call = quote!(#call.await);
+    }
+    if qualifiers.iter().any(|tt| is_ident(tt, "unsafe")) {
+        call = quote!(unsafe { #call });
+    }
+    let qualifiers = stream(qualifiers);
+    Ok(quote!(
+        #cfgs
+        #[inline]
+        #qualifiers fn #ident #generics #new_args #sig_rest {
+            #call
+        }
+    ))
+}
@@ -0,0 +1,326 @@
+//! `delegate(…)`: `impl Trait for Foo`s forwarding to the `Trait` impl of
+//! `DestructuredFieldsOf<Foo>`, through `Deref{,Mut}`.
+
+use super::*;
+
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn delegate_impls(
+    delegate: Option<&args::Delegate>,
+    StructName @ _: &Ident,
+    generics: &Generics,
+    krate: &TokenStream2,
+    cfgs: &[&Attribute],
+) -> Result<TokenStream2>
+{
+    let Some(args::Delegate { traits, .. }) = delegate else {
+        return Ok(quote!());
+    };
+    let (IntroGenerics @ _, FwdGenerics @ _, _) = generics.split_for_impl();
+    let Fields = quote!(#krate::DestructuredFieldsOf<Self>);
+    let mut ret = quote!();
+    for Trait in traits {
+        let mut generics = generics.clone();
+        let where_clause = generics.make_where_clause();
+        match well_known(Trait, &Fields, krate)? {
+            | Some((Trait, items)) => {
+                where_clause.predicates.push(parse_quote!(#Fields : #Trait));
+                ret.extend(quote!(
+                    #(#cfgs)*
+                    #[automatically_derived]
+                    impl #IntroGenerics
+                        #Trait
+                    for
+                        #StructName #FwdGenerics
+                    #where_clause
+                    {
+                        #items
+                    }
+                ));
+            },
+            // A `#[delegatable]` trait, which comes with a `macro_rules!` homonym to expand to
+            // the `impl` (given the path to this crate).
+            | None => {
+                let mut segments = Trait.segments.iter();
+                if let Some(segment) = segments.find(|it| it.arguments.is_empty().not()) {
+                    let name = segment.ident.to_string();
+                    let hint = if ["AsRef", "AsMut"].contains(&&*name) {
+                        format!(" (did you mean `{name}<…>`, or `::core::convert::{name}<…>`?)")
+                    } else {
+                        String::new()
+                    };
+                    return Err(Error::new_spanned(segment, format_args!("\
+                        generic arguments are only supported for the well-known \
+                        `AsRef` and `AsMut` traits, be it bare, or through their \
+                        `::core::convert` path{hint}\
+                    ")));
+                }
+                where_clause.predicates.push(parse_quote!(#Fields : #Trait));
+                ret.extend(quote!(
+                    #(#cfgs)*
+                    #Trait! {
+                        { #krate }
+                        { impl #IntroGenerics }
+                        { #Trait }
+                        { #StructName #FwdGenerics }
+                        { #where_clause }
+                    }
+                ));
+            },
+        }
+    }
+    Ok(ret)
+}
+
+/// The path and items of the `impl` of a well-known trait, if `Trait` is one of them.
+///
+/// Only bare names (_e.g._, `Display`, or `AsRef<[u8]>`), and `::core`-, `::alloc`-, or
+/// `::std`-rooted paths (_e.g._, `::core::fmt::Write`) qualify: any other path, such as
+/// `self::Display`, or `fmt::Write`, names a `#[delegatable]` trait of the caller.
+#[allow(clippy::redundant_pattern)]
+fn well_known(
+    Trait @ _: &Path,
+    Fields @ _: &TokenStream2,
+    krate: &TokenStream2,
+) -> Result<Option<(TokenStream2, TokenStream2)>>
+{
+    let ඞ = args::internals_path(krate);
+    let (root_and_module, last) = match Trait.segments.iter().collect::<Vec<_>>()[..] {
+        | [last] if Trait.leading_colon.is_none() => (None, last),
+        | [root, module, last]
+        if Trait.leading_colon.is_some()
+        && root.arguments.is_empty()
+        && module.arguments.is_empty()
+        => (Some((root.ident.to_string(), module.ident.to_string())), last),
+        | _ => return Ok(None),
+    };
+    let name = last.ident.to_string();
+    // Whether `::{root}::{module}` (or a bare name) is where the well-known traits of `module`
+    // live.
+    let in_module = |expected: &str| match &root_and_module {
+        // A bare `Write` is that of `io`, as with `Read`.
+        | None => (&*name, expected) != ("Write", "fmt"),
+        | Some((root, module)) => module == expected && match &**root {
+            | "core" => ["error", "io"].contains(&expected).not(),
+            | "alloc" => expected == "fmt",
+            | "std" => true,
+            | _ => false,
+        },
+    };
+    let requires_std = || -> Result<()> {
+        if cfg!(feature = "std").not() {
+            let fmt_write_hint = if root_and_module.is_none() && name == "Write" {
+                "\n\nA bare `Write` is `::std::io::Write`: for `::core::fmt::Write`, spell it out."
+            } else {
+                ""
+            };
+            return Err(Error::new_spanned(Trait, format_args!("\
+                delegating this trait requires enabling the `std` Cargo \
+                feature, like so:\n    \
+                # Cargo.toml:\n\n    \
+                [dependencies]\n    \
+                # ...\n    \
+                drop-with-owned-fields.version = \"x.y.z\"\n    \
+                drop-with-owned-fields.features = [\"std\"]\
+                {fmt_write_hint}\
+            ")));
+        }
+        Ok(())
+    };
+    let type_arg = || -> Result<&Type> {
+        match &last.arguments {
+            | PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                | GenericArgument::Type(T) => Ok(T),
+                | _ => Err(Error::new_spanned(args, "expected a type")),
+            },
+            | _ => Err(Error::new_spanned(last, format_args!("expected `{name}<T>`"))),
+        }
+    };
+    if last.arguments.is_empty().not() && ["AsRef", "AsMut"].contains(&&*name).not() {
+        return Ok(None);
+    }
+    let ret = match &*name {
+        | "Display" | "Debug" | "Binary" | "Octal" | "LowerHex" | "UpperHex" | "LowerExp"
+        | "UpperExp" if in_module("fmt") => {
+            let Trait = quote!(::core::fmt::#last);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    <#Fields as #Trait>::fmt(&**self, f)
+                }
+            ))
+        },
+        | "Write" if in_module("fmt") => {
+            let Trait = quote!(::core::fmt::Write);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                    <#Fields as #Trait>::write_str(&mut **self, s)
+                }
+
+                #[inline]
+                fn write_char(&mut self, c: char) -> ::core::fmt::Result {
+                    <#Fields as #Trait>::write_char(&mut **self, c)
+                }
+            ))
+        },
+        | "Hash" if in_module("hash") => {
+            let Trait = quote!(::core::hash::Hash);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn hash<H : ::core::hash::Hasher>(&self, state: &mut H) {
+                    <#Fields as #Trait>::hash(&**self, state)
+                }
+            ))
+        },
+        | "AsRef" if in_module("convert") => {
+            let T = type_arg()?;
+            let Trait = quote!(::core::convert::AsRef<#T>);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn as_ref(&self) -> &#T {
+                    <#Fields as #Trait>::as_ref(&**self)
+                }
+            ))
+        },
+        | "AsMut" if in_module("convert") => {
+            let T = type_arg()?;
+            let Trait = quote!(::core::convert::AsMut<#T>);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn as_mut(&mut self) -> &mut #T {
+                    <#Fields as #Trait>::as_mut(&mut **self)
+                }
+            ))
+        },
+        | "Iterator" if in_module("iter") => {
+            let Trait = quote!(::core::iter::Iterator);
+            (Trait.clone(), quote!(
+                type Item = <#Fields as #Trait>::Item;
+
+                #[inline]
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                    <#Fields as #Trait>::next(&mut **self)
+                }
+
+                #[inline]
+                fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                    <#Fields as #Trait>::size_hint(&**self)
+                }
+            ))
+        },
+        | "DoubleEndedIterator" if in_module("iter") => {
+            let Trait = quote!(::core::iter::DoubleEndedIterator);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+                    <#Fields as #Trait>::next_back(&mut **self)
+                }
+            ))
+        },
+        | "ExactSizeIterator" if in_module("iter") => {
+            let Trait = quote!(::core::iter::ExactSizeIterator);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn len(&self) -> usize {
+                    <#Fields as #Trait>::len(&**self)
+                }
+            ))
+        },
+        | "FusedIterator" if in_module("iter") => (quote!(::core::iter::FusedIterator), quote!()),
+        | "Error" if in_module("error") => {
+            requires_std()?;
+            let Trait = quote!(#ඞ::std::error::Error);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn source(&self) -> ::core::option::Option<&(dyn #Trait + 'static)> {
+                    <#Fields as #Trait>::source(&**self)
+                }
+            ))
+        },
+        | "Read" if in_module("io") => {
+            requires_std()?;
+            let io = quote!(#ඞ::std::io);
+            let Trait = quote!(#io::Read);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn read(&mut self, buf: &mut [u8]) -> #io::Result<usize> {
+                    <#Fields as #Trait>::read(&mut **self, buf)
+                }
+
+                #[inline]
+                fn read_to_end(
+                    &mut self,
+                    buf: &mut #ඞ::alloc::vec::Vec<u8>,
+                ) -> #io::Result<usize>
+                {
+                    <#Fields as #Trait>::read_to_end(&mut **self, buf)
+                }
+
+                #[inline]
+                fn read_to_string(
+                    &mut self,
+                    buf: &mut #ඞ::alloc::string::String,
+                ) -> #io::Result<usize>
+                {
+                    <#Fields as #Trait>::read_to_string(&mut **self, buf)
+                }
+
+                #[inline]
+                fn read_exact(&mut self, buf: &mut [u8]) -> #io::Result<()> {
+                    <#Fields as #Trait>::read_exact(&mut **self, buf)
+                }
+            ))
+        },
+        | "BufRead" if in_module("io") => {
+            requires_std()?;
+            let io = quote!(#ඞ::std::io);
+            let Trait = quote!(#io::BufRead);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn fill_buf(&mut self) -> #io::Result<&[u8]> {
+                    <#Fields as #Trait>::fill_buf(&mut **self)
+                }
+
+                #[inline]
+                fn consume(&mut self, amt: usize) {
+                    <#Fields as #Trait>::consume(&mut **self, amt)
+                }
+            ))
+        },
+        | "Write" if in_module("io") => {
+            requires_std()?;
+            let io = quote!(#ඞ::std::io);
+            let Trait = quote!(#io::Write);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn write(&mut self, buf: &[u8]) -> #io::Result<usize> {
+                    <#Fields as #Trait>::write(&mut **self, buf)
+                }
+
+                #[inline]
+                fn write_all(&mut self, buf: &[u8]) -> #io::Result<()> {
+                    <#Fields as #Trait>::write_all(&mut **self, buf)
+                }
+
+                #[inline]
+                fn flush(&mut self) -> #io::Result<()> {
+                    <#Fields as #Trait>::flush(&mut **self)
+                }
+            ))
+        },
+        | "Seek" if in_module("io") => {
+            requires_std()?;
+            let io = quote!(#ඞ::std::io);
+            let Trait = quote!(#io::Seek);
+            (Trait.clone(), quote!(
+                #[inline]
+                fn seek(&mut self, pos: #io::SeekFrom) -> #io::Result<u64> {
+                    <#Fields as #Trait>::seek(&mut **self, pos)
+                }
+            ))
+        },
+        | _ => return Ok(None),
+    };
+    Ok(Some(ret))
+}
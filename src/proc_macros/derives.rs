@@ -31,10 +31,25 @@ use super::*;
 ///
 ///     Using syntactical heuristics to detect this, we hard-code a re-implementation of it
 ///     that suits us.
+///
+///  3. The comparison and hashing derives (`PartialEq`, `Eq`, `PartialOrd`, `Ord`, `Hash`) are
+///     not given to `Foo` through 1., but rather become impls forwarding to those of `FooFields`
+///     (which do get derived), so that both types are guaranteed to agree with one another.
+///
+///     This is what makes the impls between `Foo` and `FooFields`, in both directions, which
+///     `PartialEq` and `PartialOrd` also get, consistent with `Foo == Foo` and `Foo < Foo`. With
+///     `borrow`, all five are forwarded, derived or not, so that a hand-written (and thus possibly
+///     disagreeing) impl on `Foo` be rejected as a conflicting one, as per the contract of
+///     `Borrow`.
+#[allow(clippy::redundant_pattern)]
 pub(crate)
 fn best_effort_compat_with_other_derives_and_attrs(
     input: &DeriveInput,
     StructNameDestructuredFields @ _: &'_ Ident,
+    struct_name_helper_module: &'_ Ident,
+    krate: &'_ TokenStream2,
+    cfgs: &'_ [&'_ Attribute],
+    borrow: bool,
 ) -> Result<TokenStream2>
 {
     let mut input = input.clone();
@@ -63,6 +78,8 @@ fn best_effort_compat_with_other_derives_and_attrs(
     let mut serialize = None;
     let mut clone = None;
     let mut default = None;
+    // The forwarded traits, along with the span of their `derive`, if any.
+    let mut forwarded: Vec<(&str, Option<Span>)> = vec![];
     all_derives.retain_mut(|path| Retain::Yes == {
         match &path.segments.last().unwrap().ident.to_string()[..] {
             | "Serialize" => {
@@ -77,6 +94,13 @@ fn best_effort_compat_with_other_derives_and_attrs(
                 default = Some(mem::replace(path, parse_quote!(a)));
                 Retain::No
             },
+            | name @ ("PartialEq" | "Eq" | "PartialOrd" | "Ord" | "Hash")
+            if is_std_path(path, if name == "Hash" { "hash" } else { "cmp" })
+            => {
+                let name = FORWARDED.iter().find(|&&it| it == name).unwrap();
+                forwarded.push((name, Some(path.segments.last().unwrap().span_location())));
+                Retain::No
+            },
             | _ => {
                 Retain::Yes
             },
@@ -117,7 +141,10 @@ fn best_effort_compat_with_other_derives_and_attrs(
         };
         let each_field_name = fields.members();
         let EachFieldTy @ _ = fields.iter().map(|f| &f.ty);
+        let each_field_cfgs = fields.iter().map(|f| utils::cfg_attrs(&f.attrs));
         ret.extend(quote!(
+            #(#cfgs)*
+            #[automatically_derived]
             impl #IntroGenerics
                 #Clone
             for
@@ -126,8 +153,9 @@ fn best_effort_compat_with_other_derives_and_attrs(
             {
                 #[inline]
                 fn clone(&self) -> Self {
-                    ::drop_with_owned_fields::DestructuredFieldsOf::<Self> {
+                    #krate::DestructuredFieldsOf::<Self> {
                         #(
+                            #(#each_field_cfgs)*
                             #each_field_name:
                                 <#EachFieldTy as #Clone>::clone(&self.#each_field_name)
                             ,
@@ -164,7 +192,10 @@ fn best_effort_compat_with_other_derives_and_attrs(
         };
         let each_field_name = fields.members();
         let EachFieldTy @ _ = fields.iter().map(|f| &f.ty);
+        let each_field_cfgs = fields.iter().map(|f| utils::cfg_attrs(&f.attrs));
         ret.extend(quote!(
+            #(#cfgs)*
+            #[automatically_derived]
             impl #IntroGenerics
                 #Default
             for
@@ -173,8 +204,9 @@ fn best_effort_compat_with_other_derives_and_attrs(
             {
                 #[inline]
                 fn default() -> Self {
-                    ::drop_with_owned_fields::DestructuredFieldsOf::<Self> {
+                    #krate::DestructuredFieldsOf::<Self> {
                         #(
+                            #(#each_field_cfgs)*
                             #each_field_name:
                                 <#EachFieldTy as #Default>::default()
                             ,
@@ -185,15 +217,170 @@ fn best_effort_compat_with_other_derives_and_attrs(
             }
         ));
     }
+    // 3. Forwarded comparisons and hashing, and cross-type comparisons.
+    if borrow {
+        for name in FORWARDED {
+            if forwarded.iter().all(|&(it, _)| it != name) {
+                forwarded.push((name, None));
+            }
+        }
+    }
+    let (IntroGenerics, FwdGenerics, _) = input.generics.split_for_impl();
+    let Fields = quote!(#struct_name_helper_module::#StructNameDestructuredFields #FwdGenerics);
+    for &(name, derive_span) in &forwarded {
+        let derived_trait_span = derive_span.unwrap_or_else(Span::call_site);
+        let Trait @ _ = match name {
+            | "Hash" => quote_spanned!(derived_trait_span=> ::core::hash::Hash),
+            | _ => {
+                let name = Ident::new(name, derived_trait_span);
+                quote_spanned!(derived_trait_span=> ::core::cmp::#name)
+            },
+        };
+        let mut where_clause =
+            input
+                .generics
+                .where_clause
+                .clone()
+                .unwrap_or_else(|| parse_quote!(where))
+        ;
+        where_clause.predicates.push(match derive_span {
+            | Some(_) => parse_quote_spanned!(derived_trait_span=>
+                #Fields : #Trait
+            ),
+            // Not a trivial bound, which would be an error for a non-generic `Foo` whose
+            // `FooFields` does not implement `Trait`, rather than making the impl vacuous.
+            | None => parse_quote!(
+                for<'__> #Fields : #Trait
+            ),
+        });
+        let Ordering = quote!(::core::cmp::Ordering);
+        let (items, cross_type_method) = match name {
+            | "PartialEq" => (
+                quote!(
+                    #[inline]
+                    fn eq(&self, other: &Self) -> bool {
+                        <#Fields as #Trait>::eq(&**self, &**other)
+                    }
+                ),
+                Some((quote!(eq), quote!(bool))),
+            ),
+            | "PartialOrd" => (
+                quote!(
+                    #[inline]
+                    fn partial_cmp(&self, other: &Self) -> ::core::option::Option<#Ordering> {
+                        <#Fields as #Trait>::partial_cmp(&**self, &**other)
+                    }
+                ),
+                Some((quote!(partial_cmp), quote!(::core::option::Option<#Ordering>))),
+            ),
+            | "Ord" => (
+                quote!(
+                    #[inline]
+                    fn cmp(&self, other: &Self) -> #Ordering {
+                        <#Fields as #Trait>::cmp(&**self, &**other)
+                    }
+                ),
+                None,
+            ),
+            | "Hash" => (
+                quote!(
+                    #[inline]
+                    fn hash<H : ::core::hash::Hasher>(&self, state: &mut H) {
+                        <#Fields as #Trait>::hash(&**self, state)
+                    }
+                ),
+                None,
+            ),
+            | _ => (quote!(), None),
+        };
+        ret.extend(quote!(
+            #(#cfgs)*
+            #[automatically_derived]
+            impl #IntroGenerics
+                #Trait
+            for
+                #StructName #FwdGenerics
+            #where_clause
+            {
+                #items
+            }
+        ));
+        if let (Some(_), Some((method, Ret))) = (derive_span, cross_type_method) {
+            ret.extend(quote!(
+                #(#cfgs)*
+                #[automatically_derived]
+                impl #IntroGenerics
+                    #Trait<#Fields>
+                for
+                    #StructName #FwdGenerics
+                #where_clause
+                {
+                    #[inline]
+                    fn #method(&self, other: &#Fields) -> #Ret {
+                        <#Fields as #Trait>::#method(&**self, other)
+                    }
+                }
+
+                #(#cfgs)*
+                #[automatically_derived]
+                impl #IntroGenerics
+                    #Trait<#StructName #FwdGenerics>
+                for
+                    #Fields
+                #where_clause
+                {
+                    #[inline]
+                    fn #method(&self, other: &#StructName #FwdGenerics) -> #Ret {
+                        <#Fields as #Trait>::#method(self, &**other)
+                    }
+                }
+            ));
+        }
+    }
     // 1. the derives hack:
     if all_derives.is_empty().not() {
         input.attrs.insert(0, parse_quote!(
             #[derive(#(#all_derives),*)]
         ));
     }
+    let ඞ = args::internals_path(krate);
     input.attrs.push(parse_quote!(
-        #[::drop_with_owned_fields::ඞ::annihilate]
+        #[#ඞ::annihilate]
     ));
     input.to_tokens(&mut ret);
     Ok(ret)
 }
+
+/// The comparison and hashing traits whose impls on `Foo` forward to those of `FooFields`.
+const FORWARDED: [&str; 5] = ["PartialEq", "Eq", "PartialOrd", "Ord", "Hash"];
+
+/// Whether a `derive`d `path` designates the `::core::{module}` derive of its last segment: either
+/// through that very path (be it `core`-, or `std`-rooted), or directly, through the prelude.
+fn is_std_path(path: &Path, module: &str) -> bool {
+    match &path.segments.iter().map(|it| it.ident.to_string()).collect::<Vec<_>>()[..] {
+        | [_] => path.leading_colon.is_none(),
+        | [root, module_, _] => ["core", "std"].contains(&&**root) && module_ == module,
+        | _ => false,
+    }
+}
+
+/// Whether `#[derive(…)]` features a `Trait` (as per the last segment of its path).
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn is_derived(attrs: &[Attribute], Trait @ _: &str) -> bool {
+    attrs.iter().any(|attr| match &attr.meta {
+        | Meta::List(meta) if meta.path.segments.last().unwrap().ident == "derive" => {
+            Parser::parse2(
+                |input: ParseStream<'_>| Punctuated::<_, Token![,]>::parse_terminated_with(
+                    input,
+                    Path::parse_mod_style,
+                ),
+                meta.tokens.clone(),
+            )
+            .is_ok_and(|derives| {
+                derives.iter().any(|path| path.segments.last().unwrap().ident == Trait)
+            })
+        },
+        | _ => false,
+    })
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+/// The fields of `FooඞFields` being dropped in declaration order, fields left in the
+/// `DropWithOwnedFields` logic are rather moved into a hidden, drop-ordered, twin `struct`, whose
+/// declaration order is the configured drop order: this computes it, as indices into `fields`, if
+/// configured.
+///
+/// The `FooඞFields` declaration itself is left untouched, so that neither its derives (`Ord`,
+/// `Hash`, `Debug`, …), nor its construction, are affected.
+pub(crate)
+fn drop_order(
+    fields: &Fields,
+    drop_order: Option<&args::DropOrder>,
+    each_field_args: &[args::FieldArgs],
+) -> Result<Option<Vec<usize>>>
+{
+    let drop_last = || each_field_args.iter().filter_map(|it| it.drop_last.as_ref());
+    let span = match (drop_order, drop_last().next()) {
+        | (Some(args::DropOrder::Explicit(kw, _)), _) => kw.span,
+        | (Some(args::DropOrder::Reverse(kw)), _) => kw.span,
+        | (None, Some(kw)) => kw.span,
+        | (None, None) => return Ok(None),
+    };
+    let Fields::Named(FieldsNamed { named, .. }) = fields else {
+        return Err(Error::new(span, "configuring the drop order requires named fields"));
+    };
+    if cfg!(feature = "drop-sugar").not() {
+        return Err(Error::new(span, "\
+            configuring the drop order requires the `impl Drop` sugar, and thus, enabling the \
+            `drop-sugar` Cargo feature, like so:\n    \
+            # Cargo.toml:\n\n    \
+            [dependencies]\n    \
+            # ...\n    \
+            drop-with-owned-fields.version = \"x.y.z\"\n    \
+            drop-with-owned-fields.features = [\"drop-sugar\"]\
+        "));
+    }
+
+    let is_drop_last = |i: usize| each_field_args[i].drop_last.is_some();
+    let mut order = Vec::with_capacity(named.len());
+    match drop_order {
+        | None => order.extend(0 .. named.len()),
+        | Some(args::DropOrder::Reverse(_)) => order.extend((0 .. named.len()).rev()),
+        | Some(args::DropOrder::Explicit(_, listed)) => {
+            for name in listed {
+                let Some(i) = named.iter().position(|Field { ident, .. }| {
+                    ident.as_ref().unwrap().unraw() == name.unraw()
+                })
+                else {
+                    return Err(Error::new_spanned(name, "no such field"));
+                };
+                if order.contains(&i) {
+                    return Err(Error::new_spanned(name, "duplicate field"));
+                }
+                if is_drop_last(i) {
+                    return Err(Error::new_spanned(name, "\
+                        field already marked as `#[drop_with_owned_fields(drop_last)]`\
+                    "));
+                }
+                order.push(i);
+            }
+            // The unlisted fields get dropped afterwards, in declaration order.
+            let unlisted = (0 .. named.len()).filter(|i| order.contains(i).not());
+            order.extend(unlisted.collect::<Vec<_>>());
+        },
+    }
+    order.retain(|&i| is_drop_last(i).not());
+    order.extend((0 .. named.len()).filter(|&i| is_drop_last(i)));
+    Ok(Some(order))
+}
@@ -1,115 +1,237 @@
+//! The `impl Drop` sugar only ever needs to understand
+//! `impl<…> Drop for Ty where … { fn drop(PAT: _) BODY }`, so rather than requiring `syn/full`
+//! (and its compile-time cost) to parse an `ItemImpl`, it is handled by walking the token trees.
+//! See [`token_walking`].
+
 use super::*;
+use ::proc_macro2::{Delimiter, Group};
+use token_walking::*;
+
+const NOT_A_DROP_IMPL: &str = "expected a `Drop` impl";
+
+const NOT_A_DESTRUCTURING_OF_SELF: &str =
+    "expected a `Self { fields… }` or `Self(fields…)` destructuring pattern"
+;
 
 pub(crate)
 fn handle(
     args: TokenStream2,
-    mut impl_: ItemImpl,
+    attrs: Vec<Attribute>,
+    impl_: TokenStream2,
 ) -> Result<TokenStream2>
 {
-    let _: parse::Nothing = parse2(args)?;
+    let krate = args::crate_path(Parser::parse2(
+        |input: ParseStream<'_>| {
+            let krate = input.is_empty().not().then(|| input.parse()).transpose()?;
+            let _: Option<Token![,]> = input.parse()?;
+            let _: parse::Nothing = input.parse()?;
+            Ok(krate)
+        },
+        args,
+    )?.as_ref());
+    let ඞ = args::internals_path(&krate);
     // 1. Validate we are dealing with an actual `Drop` impl (not done in `Parse`)
     // to keep that common code leaner.
     // 2. Replace the `Drop` sugar accordingly:
     //      - `Drop -> DropWithOwnedFields`,
-    //      - `Self { … } -> DestructuredFieldsOf::<Self> { … }`,
+    //      - `Self { … } -> DropOrderedFieldsOf::<Self> { … }`, moving the fields into the
+    //        drop-ordered `struct` beforehand, so that the ones left over be dropped in the
+    //        configured order (`Self(…) -> DestructuredFieldsOf::<Self>(…)`, otherwise),
     //      - `: _ -> : DestructuredFieldsOf<Self>`,
-    // 1.1. `impl Drop`
-    const NOT_A_DROP_IMPL: &str = "expected a `Drop` impl";
-    match &mut impl_.trait_ {
-        | Some((None, Drop @ _, _))
-        if Drop.segments.len() == 1
-        && matches!(
-            Drop.segments.last().unwrap(),
-            PathSegment {
-                ident,
-                arguments: PathArguments::None,
-            }
-            if ident == "Drop"
-        ) => {
-            // 2.1
-            *Drop = parse_quote_spanned!(Drop.span_location()=>
-                ::drop_with_owned_fields::DropWithOwnedFields
-            );
-        },
-        | Some((_, _, for_)) => return Err(Error::new_spanned(for_, NOT_A_DROP_IMPL)),
-        | None => return Err(Error::new_spanned(impl_.self_ty, NOT_A_DROP_IMPL)),
+    //    and mark the type as using the sugar, for the drop order not to be silently ignored
+    //    otherwise.
+    let mut tts = impl_.into_iter().collect::<Vec<TT>>();
+    let Some(TT::Group(body)) = tts.pop() else {
+        return Err(Error::new(Span::call_site(), NOT_A_DROP_IMPL));
+    };
+    // `impl` (already peeked by `Input::parse()`), then `<…>`, if any.
+    let mut i = 1;
+    if is_punct(tts.get(i), '<') {
+        i += angle_bracketed_len(&tts[i ..]);
     }
+    let where_ =
+        position_at_depth_0(&tts[i ..], |tt| is_ident(tt, "where"))
+            .map_or(tts.len(), |j| i + j)
+    ;
+
+    // 1.1. `impl Drop`
+    let Some(for_) = position_at_depth_0(&tts[i .. where_], |tt| is_ident(tt, "for")) else {
+        return Err(Error::new_spanned(stream(&tts[i .. where_]), NOT_A_DROP_IMPL));
+    };
+    let for_ = i + for_;
+    let Drop = match &tts[i .. for_] {
+        | [Drop @ TT::Ident(ident)] if ident == "Drop" => Drop.clone(),
+        // `::Drop`
+        | [leading_colon @ TT::Punct(_), TT::Punct(_), TT::Ident(ident)]
+        if is_path_separator(&tts[i .. for_], 0) && ident == "Drop"
+        => leading_colon.clone(),
+        | _ => return Err(Error::new(tts[for_].span(), NOT_A_DROP_IMPL)),
+    };
+    // 2.1
+    let DropWithOwnedFields = quote_spanned!(Drop.span_location()=>
+        #krate::DropWithOwnedFields
+    );
+    let mut drop_sugar_impl = tts.clone();
+    drop_sugar_impl.splice(i .. for_, quote_spanned!(Drop.span_location()=>
+        #ඞ::DropSugar
+    ));
+    tts.splice(i .. for_, DropWithOwnedFields);
 
     // 1.2. One `fn` item, of `fn drop`.
-    let mut fns = impl_.items.iter_mut();
-    let fn_ = match (fns.next(), fns.next()) {
-        | (Some(ImplItem::Fn(fn_)), None) => fn_,
-        | (None, _) => return Err(Error::new(
-            impl_.brace_token.span.close(),
+    let items = body.stream().into_iter().collect::<Vec<TT>>();
+    let mut start = 0;
+    // Skip the inner attributes, if any.
+    while is_punct(items.get(start), '#') && is_punct(items.get(start + 1), '!') {
+        start += 3;
+    }
+    let first = start .. start + item_len(&items[start ..]);
+    let second = first.end .. first.end + item_len(&items[first.end ..]);
+    let fn_ = match (first.is_empty(), second.is_empty()) {
+        | (true, _) => return Err(Error::new(
+            body.span_close(),
             "expected at least one `fn`",
         )),
-        | (Some(extraneous_item), None)
-        | (Some(_), Some(extraneous_item)) => return Err(Error::new_spanned(
-            extraneous_item,
+        | (false, false) => return Err(Error::new_spanned(
+            stream(&items[second]),
             "unexpected item",
         )),
+        | (false, true) => match fn_item(&items[first.clone()]) {
+            | Some(fn_) => fn_,
+            | None => return Err(Error::new_spanned(
+                stream(&items[first]),
+                "unexpected item",
+            )),
+        },
     };
-    if fn_.sig.ident != "drop" {
-        return Err(Error::new_spanned(&fn_.sig.ident, "expected `drop`"));
+    let (fn_start, drop_, sig_args) = (first.start + fn_.start, &fn_.ident, &fn_.args);
+    if drop_ != "drop" {
+        return Err(Error::new_spanned(drop_, "expected `drop`"));
     }
 
     // 1.3. A single `Self { … }: _` arg.
-    const NOT_A_DESTRUCTURING_OF_SELF: &str =
-        "expected a `Self { fields… }` or `Self(fields…)` destructuring pattern"
-    ;
-    let mut args = fn_.sig.inputs.iter_mut();
-    match (args.next(), args.next()) {
-        | (
-            Some(FnArg::Typed(PatType {
-                attrs: _,
-                pat: Self_,
-                colon_token: _,
-                ty,
-            })),
-            None,
-        )
-        => {
-            //                            heh
-            //                            v
-            if matches!(&**ty, Type::Infer(_)).not() {
-                return Err(Error::new_spanned(&**ty, "expected `_`"));
-            }
-            match &mut **Self_ {
-                | Pat::Struct(PatStruct { qself: None, path: Self_, .. })
-                | Pat::TupleStruct(PatTupleStruct { qself: None, path: Self_, .. })
-                if Self_.is_ident("Self")
-                => {
-                    // 2.3
-                    **ty = parse_quote_spanned!(ty.span_location()=>
-                        ::drop_with_owned_fields::DestructuredFieldsOf<#Self_>
-                    );
-                    // 2.2
-                    *Self_ = parse_quote_spanned!(Self_.span_location()=>
-                        ::drop_with_owned_fields::DestructuredFieldsOf<#Self_>
-                    );
-                },
-                ill_formed => return Err(Error::new_spanned(
-                    ill_formed,
-                    NOT_A_DESTRUCTURING_OF_SELF,
-                )),
-            }
-        }
-
-        | (Some(ill_formed), None) => return Err(Error::new_spanned(
-            ill_formed,
+    let args = split_at_depth_0_commas(sig_args.stream());
+    let arg = match &args[..] {
+        | [arg] => arg,
+        | [] => return Err(Error::new(
+            sig_args.span_close(),
             NOT_A_DESTRUCTURING_OF_SELF,
         )),
-
-        | (None, _) => return Err(Error::new(
-            fn_.sig.paren_token.span.close(),
-            NOT_A_DESTRUCTURING_OF_SELF,
-        )),
-        | (Some(_), Some(extraneous_arg)) => return Err(Error::new_spanned(
-            extraneous_arg,
+        | [_, extraneous_arg, ..] => return Err(Error::new_spanned(
+            stream(extraneous_arg),
             "extraneous `fn` arg",
         )),
+    };
+    let mut attrs_len = 0;
+    while is_punct(arg.get(attrs_len), '#') {
+        attrs_len += 2;
+    }
+    let (arg_attrs, typed_arg) = arg.split_at(attrs_len);
+    let colon =
+        (! is_receiver(typed_arg))
+            .then(|| position_at_depth_0(typed_arg, |tt| is_punct(Some(tt), ':')))
+            .flatten()
+    ;
+    let Some(colon) = colon else {
+        return Err(Error::new_spanned(stream(arg), NOT_A_DESTRUCTURING_OF_SELF));
+    };
+    let (Self_, ty) = (&typed_arg[.. colon], &typed_arg[colon + 1 ..]);
+    //                    heh
+    //                    v
+    if matches!(ty, [TT::Ident(ident)] if ident == "_").not() {
+        return Err(Error::new_spanned(stream(ty), "expected `_`"));
     }
+    let (Self_, fields) = match Self_ {
+        | [TT::Ident(Self_), TT::Group(fields)]
+        if Self_ == "Self"
+        && matches!(fields.delimiter(), Delimiter::Brace | Delimiter::Parenthesis)
+        => (Self_, fields),
+        | ill_formed => return Err(Error::new_spanned(
+            stream(ill_formed),
+            NOT_A_DESTRUCTURING_OF_SELF,
+        )),
+    };
+    // 2.3
+    let ty = quote_spanned!(ty[0].span_location()=>
+        #krate::DestructuredFieldsOf<#Self_>
+    );
+    // 2.2
+    let mut items = items;
+    let sig_args = if fields.delimiter() == Delimiter::Brace {
+        let DropOrderedFieldsOf = quote_spanned!(Self_.span_location()=>
+            #ඞ::DropOrderedFieldsOf::<#Self_>
+        );
+        let fields_var = Ident::new("fields", Span::mixed_site());
+        let TT::Group(fn_body) = &items[first.end - 1] else { unreachable!() };
+        let mut new_fn_body = Group::new(Delimiter::Brace, quote!(
+            let #fields_var =
+                <#Self_ as #krate::DestructureFields>::__drop_ordered(#fields_var)
+            ;
+            #(#arg_attrs)*
+            let #DropOrderedFieldsOf #fields = #fields_var;
+            #fn_body
+        ));
+        new_fn_body.set_span(fn_body.span());
+        items[first.end - 1] = TT::Group(new_fn_body);
+        quote!(#fields_var : #ty)
+    } else {
+        let DestructuredFieldsOf = quote_spanned!(Self_.span_location()=>
+            #krate::DestructuredFieldsOf::<#Self_>
+        );
+        quote!(#(#arg_attrs)* #DestructuredFieldsOf #fields : #ty)
+    };
+    let mut sig_args = Group::new(Delimiter::Parenthesis, sig_args);
+    sig_args.set_span(fn_.args.span());
+    items[fn_start + fn_.args_idx] = TT::Group(sig_args);
+    let mut new_body = Group::new(Delimiter::Brace, items.into_iter().collect());
+    new_body.set_span(body.span());
+    tts.push(TT::Group(new_body));
 
-    let ret = impl_.into_token_stream();
-    Ok(ret)
+    let cfgs = utils::cfg_attrs(&attrs);
+    Ok(quote!(
+        #(#attrs)*
+        #(#tts)*
+
+        #(#cfgs)*
+        #(#drop_sugar_impl)* {}
+    ))
+}
+
+/// The parts of a `fn` item we care about.
+struct FnItem {
+    /// Offset of the `fn` keyword within the item.
+    start: usize,
+    ident: Ident,
+    args: Group,
+    /// Offset of the `args` within the `fn` item, from its `fn` keyword.
+    args_idx: usize,
+}
+
+/// `#[attrs]… qualifiers… fn ident<…>(args…) -> Ret where … { … }`
+fn fn_item(item: &[TT]) -> Option<FnItem> {
+    let start = fn_keyword(item)?;
+    let item = &item[start ..];
+    let TT::Ident(ident) = item.get(1)? else { return None };
+    let args_idx = position_at_depth_0(&item[2 ..], |tt| matches!(
+        tt,
+        TT::Group(g) if g.delimiter() == Delimiter::Parenthesis
+    ))? + 2;
+    let TT::Group(args) = &item[args_idx] else { unreachable!() };
+    matches!(item.last(), Some(TT::Group(g)) if g.delimiter() == Delimiter::Brace).then(|| FnItem {
+        start,
+        ident: ident.clone(),
+        args: args.clone(),
+        args_idx,
+    })
+}
+
+/// The amount of token trees making up the `<…>` at the beginning of `tts`.
+fn angle_bracketed_len(tts: &[TT]) -> usize {
+    let mut depth = 0;
+    for k in 0 .. tts.len() {
+        depth = angle_depth(tts, k, depth);
+        if depth == 0 {
+            return k + 1;
+        }
+    }
+    tts.len()
 }
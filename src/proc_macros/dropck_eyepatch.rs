@@ -0,0 +1,91 @@
+use super::*;
+
+/// Returns the generic parameters of the input `generics` but for the `unsafe(may_dangle(…))`-listed
+/// type parameters, as types (`&'a ()` for a lifetime), for the `unsafe impl ඞ::Eyepatch<(…)>`
+/// which lets the `ManuallyDropFields` of `Self` be dropped while the other ones dangle.
+///
+/// The soundness of `#[may_dangle] T` hinges on the drop logic not being able to do anything with
+/// a `T` besides moving it around and dropping it. We cannot check the whole body of the user's
+/// `DropWithOwnedFields` logic (hence the `unsafe`), but we can at least make sure the definition
+/// does not hand out any capability over `T`, by rejecting any non-trivial bound mentioning it:
+/// since the `impl DropWithOwnedFields` has to be as general as the `struct` definition itself
+/// (for the `impl Drop` to delegate to it), it will then be fully parametric over `T`.
+///
+/// Ownership of the `T`s, on the other hand, is still conveyed to dropck by the
+/// `ManuallyDropFields`, despite the `ManuallyDrop` therein.
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn non_dangling_params(
+    generics: &Generics,
+    args::MayDangle { may_dangle, params, .. }: &args::MayDangle,
+) -> Result<Vec<TokenStream2>>
+{
+    if cfg!(feature = "dropck-eyepatch").not() {
+        return Err(Error::new_spanned(may_dangle, "\
+            support for this arg requires enabling the `dropck-eyepatch` Cargo \
+            feature (nightly-only), like so:\n    \
+            # Cargo.toml:\n\n    \
+            [dependencies]\n    \
+            # ...\n    \
+            drop-with-owned-fields.version = \"x.y.z\"\n    \
+            drop-with-owned-fields.features = [\"dropck-eyepatch\"]\
+        "));
+    }
+    for T @ _ in params {
+        let Some(TypeParam { bounds, .. }) = generics.type_params().find(|it| it.ident == *T) else {
+            return Err(Error::new_spanned(T, "not a type parameter of this `struct`"));
+        };
+        if let Some(bound) = bounds.iter().find(|bound| is_trivial(bound).not()) {
+            return Err(Error::new_spanned(bound, BOUNDED_MAY_DANGLE));
+        }
+        let other_params_bounds =
+            generics
+                .type_params()
+                .filter(|it| it.ident != *T)
+                .flat_map(|it| &it.bounds)
+        ;
+        for bound in other_params_bounds {
+            if utils::mentions(bound.to_token_stream(), T) {
+                return Err(Error::new_spanned(bound, BOUNDED_MAY_DANGLE));
+            }
+        }
+        for predicate in generics.where_clause.iter().flat_map(|it| &it.predicates) {
+            match predicate {
+                | WherePredicate::Type(PredicateType {
+                    lifetimes: None,
+                    bounded_ty: Type::Path(TypePath { qself: None, path }),
+                    bounds,
+                    ..
+                })
+                if path.is_ident(T) && bounds.iter().all(is_trivial)
+                => {},
+                | _ if utils::mentions(predicate.to_token_stream(), T) => {
+                    return Err(Error::new_spanned(predicate, BOUNDED_MAY_DANGLE));
+                },
+                | _ => {},
+            }
+        }
+    }
+    let ret = generics.params.iter().filter_map(|param| match param {
+        | GenericParam::Lifetime(LifetimeParam { lifetime, .. }) => Some(quote!(&#lifetime ())),
+        | GenericParam::Type(TypeParam { ident: T @ _, .. }) => {
+            params.iter().all(|it| it != T).then(|| quote!(::core::marker::PhantomData<#T>))
+        },
+        | GenericParam::Const(_) => None,
+    });
+    Ok(ret.collect())
+}
+
+const BOUNDED_MAY_DANGLE: &str = "\
+    `may_dangle` type parameters cannot be involved in trait bounds, lest the drop logic be able \
+    to access dangling data through them\
+";
+
+/// `'lifetime` or `?Sized` bounds.
+fn is_trivial(bound: &TypeParamBound) -> bool {
+    matches!(
+        bound,
+        | TypeParamBound::Lifetime(_)
+        | TypeParamBound::Trait(TraitBound { modifier: TraitBoundModifier::Maybe(_), .. })
+    )
+}
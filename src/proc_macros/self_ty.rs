@@ -0,0 +1,88 @@
+//! `Self`, in the field types (and bounds) of the annotated `struct`, is meant to be `Foo<…>`, but
+//! it would otherwise become `FooFields<…>` once the fields are moved to the companion `struct`
+//! (_e.g._, an `Option<Box<Self>>` would then be missing the `Drop` logic of `Foo`).
+
+use super::*;
+use ::proc_macro2::{Group, Spacing};
+
+/// Replaces `Self` with `Foo<…>` in the field types, the bounds on the generic parameters, and the
+/// `where` clauses of `input`.
+#[allow(clippy::redundant_pattern)]
+pub(crate)
+fn rewrite_Self(input: &mut DeriveInput) -> Result<()> {
+    let Foo = {
+        let StructName @ _ = &input.ident;
+        let (_, FwdGenerics @ _, _) = input.generics.split_for_impl();
+        quote!(#StructName #FwdGenerics)
+    };
+    if let Data::Struct(DataStruct { fields, .. }) = &mut input.data {
+        for Field { ty, .. } in fields {
+            rewrite(ty, Type::parse, &Foo)?;
+        }
+    }
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(TypeParam { bounds, .. }) = param {
+            rewrite(bounds, Punctuated::parse_terminated, &Foo)?;
+        }
+    }
+    if let Some(where_clause) = &mut input.generics.where_clause {
+        rewrite(&mut where_clause.predicates, Punctuated::parse_terminated, &Foo)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::redundant_pattern)]
+fn rewrite<T : ToTokens>(
+    node: &mut T,
+    parser: impl Parser<Output = T>,
+    Foo @ _: &TokenStream2,
+) -> Result<()>
+{
+    let tokens = node.to_token_stream();
+    if mentions_Self(tokens.clone()) {
+        *node = parser.parse2(replace_Self(tokens, Foo))?;
+    }
+    Ok(())
+}
+
+fn mentions_Self(tokens: TokenStream2) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        | TT::Ident(ident) => ident == "Self",
+        | TT::Group(group) => mentions_Self(group.stream()),
+        | _ => false,
+    })
+}
+
+/// `Self` becomes `Foo<…>`, or `<Foo<…>>` when followed by `::` (as in `Self::Assoc`).
+#[allow(clippy::redundant_pattern)]
+fn replace_Self(tokens: TokenStream2, Foo @ _: &TokenStream2) -> TokenStream2 {
+    let mut tts = tokens.into_iter().peekable();
+    let mut ret = TokenStream2::new();
+    while let Some(tt) = tts.next() {
+        match tt {
+            | TT::Ident(Self_) if Self_ == "Self" => {
+                let Foo = Foo.clone().into_iter().map(|mut tt| {
+                    tt.set_span(tt.span().located_at(Self_.span()));
+                    tt
+                });
+                let is_path_prefix = matches!(
+                    tts.peek(),
+                    Some(TT::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint
+                );
+                if is_path_prefix {
+                    ret.extend(quote_spanned!(Self_.span_location()=> < #(#Foo)* >));
+                } else {
+                    ret.extend(Foo);
+                }
+            },
+            | TT::Group(group) => {
+                let stream = replace_Self(group.stream(), Foo);
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                ret.extend([TT::Group(new_group)]);
+            },
+            | _ => ret.extend([tt]),
+        }
+    }
+    ret
+}
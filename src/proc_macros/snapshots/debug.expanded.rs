@@ -0,0 +1,114 @@
+#[doc = r" Docs."]
+#[::drop_with_owned_fields::ඞ::annihilate]
+pub struct Logger<'out, T: ::core::fmt::Display = String> where T: Clone,
+{
+    pub out: &'out mut String,
+    lines: Vec<T>,
+}
+#[doc(inline)]
+pub use _Loggerඞdrop_with_owned_fields::Logger;
+pub use _Loggerඞdrop_with_owned_fields::LoggerFields;
+mod _Loggerඞdrop_with_owned_fields {
+    use super::*;
+    #[doc = r" Docs."]
+    pub struct LoggerFields<'out, T: ::core::fmt::Display = String> where T: Clone,
+    {
+        pub out: &'out mut String,
+        pub(super) lines: Vec<T>,
+    }
+    #[doc = r" Docs."]
+    #[repr(transparent)]
+    pub struct Logger<'out, T: ::core::fmt::Display = String> where T: Clone,
+    {
+        manually_drop_fields: ::drop_with_owned_fields::ඞ::ManuallyDropFields<Self, (), Self>,
+    }
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::drop_with_owned_fields::ඞ::DropFieldsHook for Logger<'out, T> where T: Clone,
+    {
+        #[inline]
+        fn drop_fields(fields: Self::Fields) {
+            <Self as ::drop_with_owned_fields::DropWithOwnedFields>::drop(fields);
+        }
+    }
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::drop_with_owned_fields::ඞ::drop_with_owned_fields_annotation for Logger<'out, T> where T: Clone,
+    {}
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::drop_with_owned_fields::DestructureFields for Logger<'out, T> where T: Clone,
+    {
+        type Fields = LoggerFields<'out, T>;
+        const FIELD_NAMES: &'static [&'static str] = &["out", "lines"];
+        #[inline]
+        fn for_each_field_ref(&self, visitor: &mut impl ::drop_with_owned_fields::VisitField,) {
+            visitor.visit_field("out", &self.manually_drop_fields.out,);
+            visitor.visit_field("lines", &self.manually_drop_fields.lines,);
+        }
+        #[inline]
+        fn for_each_field_mut(&mut self, visitor: &mut impl ::drop_with_owned_fields::VisitFieldMut,) {
+            visitor.visit_field_mut("out", &mut self.manually_drop_fields.out,);
+            visitor.visit_field_mut("lines", &mut self.manually_drop_fields.lines,);
+        }
+        #[inline]
+        fn __fields_ref(&self) -> &Self::Fields {
+            &*self.manually_drop_fields
+        }
+        #[inline]
+        fn __fields_mut(&mut self) -> &mut Self::Fields {
+            &mut *self.manually_drop_fields
+        }
+        type __DropOrdered = LoggerFields<'out, T>;
+        #[inline]
+        fn __drop_ordered(fields: Self::Fields) -> Self::__DropOrdered {
+            fields
+        }
+    }
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::core::convert::From<LoggerFields<'out, T>,> for Logger<'out, T> where T: Clone,
+    {
+        #[inline]
+        fn from(this: LoggerFields<'out, T>) -> Self {
+            this.into()
+        }
+    }
+    impl<'out, T: ::core::fmt::Display> LoggerFields<'out, T> where T: Clone,
+    {
+        #[doc = r" Wraps these fields back into the type with the `DropWithOwnedFields` logic."]
+        #[inline]
+        #[must_use]
+        pub const fn into(self) -> Logger<'out, T> {
+            Logger {
+                manually_drop_fields: ::drop_with_owned_fields::ඞ::ManuallyDropFields::new(self),
+            }
+        }
+    }
+    impl<'out, T: ::core::fmt::Display> Logger<'out, T> where T: Clone,
+    {
+        #[inline]
+        pub(crate) fn destructure_fields_disabling_impl_drop(self: Logger<'out, T>) -> LoggerFields<'out, T> {
+            let Logger {
+                manually_drop_fields
+            }
+            = self;
+            ::drop_with_owned_fields::ඞ::ManuallyDropFields::into_inner(manually_drop_fields)
+        }
+    }
+    impl<'out, T: ::core::fmt::Display> Logger<'out, T> where T: Clone,
+    {}
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::core::ops::Deref for Logger<'out, T> where T: Clone,
+    {
+        type Target = ::drop_with_owned_fields::DestructuredFieldsOf<Self>;
+        #[inline]
+        fn deref(&self) -> &Self::Target {
+            &*self.manually_drop_fields
+        }
+    }
+    #[automatically_derived]
+    impl<'out, T: ::core::fmt::Display> ::core::ops::DerefMut for Logger<'out, T> where T: Clone,
+    {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut *self.manually_drop_fields
+        }
+    }
+}
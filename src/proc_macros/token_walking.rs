@@ -0,0 +1,136 @@
+//! Helpers to walk token trees, for the inputs which would otherwise require `syn/full` to parse.
+//!
+//! Since `rustc` only hands well-formed items to attribute macros, the walker only needs to
+//! locate things, not to validate the Rust grammar.
+
+use super::*;
+use ::proc_macro2::{Delimiter, Spacing};
+
+/// Offset of the `fn` keyword of an item, if it is a `fn` item.
+pub(crate)
+fn fn_keyword(item: &[TT]) -> Option<usize> {
+    let mut j = 0;
+    while is_punct(item.get(j), '#') {
+        j += 2;
+    }
+    loop {
+        match item.get(j)? {
+            | TT::Ident(ident) if ident == "fn" => return Some(j),
+            | TT::Ident(ident)
+            if ["pub", "crate", "default", "const", "async", "unsafe", "extern"]
+                .iter()
+                .any(|qualifier| ident == qualifier)
+            => {},
+            | TT::Literal(_) => {},
+            | TT::Group(g) if g.delimiter() == Delimiter::Parenthesis => {},
+            | _ => return None,
+        }
+        j += 1;
+    }
+}
+
+/// The amount of token trees making up the first item of `items` (`0` if there are none).
+pub(crate)
+fn item_len(items: &[TT]) -> usize {
+    let is_fn = fn_keyword(items).is_some();
+    let mut depth = 0_usize;
+    for (k, tt) in items.iter().enumerate() {
+        match tt {
+            | TT::Punct(p) if p.as_char() == ';' && depth == 0 => return k + 1,
+            // Only the signature of a `fn` may feature a brace-delimited group within `<…>`.
+            | TT::Punct(_) if is_fn => depth = angle_depth(items, k, depth),
+            | TT::Group(g)
+            if g.delimiter() == Delimiter::Brace
+            && depth == 0
+            && (is_fn || is_punct(k.checked_sub(1).and_then(|k| items.get(k)), '!'))
+            => return k + 1,
+            | _ => {},
+        }
+    }
+    items.len()
+}
+
+/// The `<…>`-nesting depth after `tts[k]`, given the one before it.
+pub(crate)
+fn angle_depth(tts: &[TT], k: usize, depth: usize) -> usize {
+    match &tts[k] {
+        | TT::Punct(p) if p.as_char() == '<' => depth + 1,
+        // Careful with the `>` of `->`.
+        | TT::Punct(p) if p.as_char() == '>' => match k.checked_sub(1).map(|k| &tts[k]) {
+            | Some(TT::Punct(arrow)) if arrow.as_char() == '-' && arrow.spacing() == Spacing::Joint
+            => depth,
+            | _ => depth.saturating_sub(1),
+        },
+        | _ => depth,
+    }
+}
+
+/// Finds the first token tree satisfying `predicate` outside of any `<…>`.
+pub(crate)
+fn position_at_depth_0(tts: &[TT], mut predicate: impl FnMut(&TT) -> bool) -> Option<usize> {
+    let mut depth = 0;
+    for (k, tt) in tts.iter().enumerate() {
+        if depth == 0 && predicate(tt) && is_path_separator(tts, k).not() {
+            return Some(k);
+        }
+        depth = angle_depth(tts, k, depth);
+    }
+    None
+}
+
+/// Whether `tts[k]` is (part of) a `::`.
+pub(crate)
+fn is_path_separator(tts: &[TT], k: usize) -> bool {
+    let is_joint_colon = |tt: Option<&TT>| matches!(
+        tt,
+        Some(TT::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint
+    );
+    is_punct(Some(&tts[k]), ':') && (
+        (is_joint_colon(Some(&tts[k])) && is_punct(tts.get(k + 1), ':'))
+        || is_joint_colon(k.checked_sub(1).and_then(|k| tts.get(k)))
+    )
+}
+
+/// Splits a `fn` args list, ignoring the trailing comma, if any.
+pub(crate)
+fn split_at_depth_0_commas(tts: TokenStream2) -> Vec<Vec<TT>> {
+    let mut tts = &tts.into_iter().collect::<Vec<TT>>()[..];
+    let mut args = vec![];
+    while tts.is_empty().not() {
+        let end = position_at_depth_0(tts, |tt| is_punct(Some(tt), ',')).unwrap_or(tts.len());
+        args.push(tts[.. end].to_vec());
+        tts = tts.get(end + 1 ..).unwrap_or_default();
+    }
+    args
+}
+
+/// `self`, `mut self`, `&self`, `&'a mut self`, `self: Ty`, …
+pub(crate)
+fn is_receiver(typed_arg: &[TT]) -> bool {
+    let mut tts = typed_arg;
+    if is_punct(tts.first(), '&') {
+        tts = &tts[1 ..];
+        if is_punct(tts.first(), '\'') {
+            tts = &tts[2 ..];
+        }
+    }
+    if matches!(tts.first(), Some(tt) if is_ident(tt, "mut")) {
+        tts = &tts[1 ..];
+    }
+    matches!(tts.first(), Some(tt) if is_ident(tt, "self"))
+}
+
+pub(crate)
+fn is_ident(tt: &TT, name: &str) -> bool {
+    matches!(tt, TT::Ident(ident) if ident == name)
+}
+
+pub(crate)
+fn is_punct(tt: Option<&TT>, c: char) -> bool {
+    matches!(tt, Some(TT::Punct(p)) if p.as_char() == c)
+}
+
+pub(crate)
+fn stream(tts: &[TT]) -> TokenStream2 {
+    tts.iter().cloned().collect()
+}
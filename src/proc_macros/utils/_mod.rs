@@ -77,3 +77,21 @@ trait AlsoExt : Sized {
     }
 }
 impl<T> AlsoExt for T {}
+
+/// Whether the given `ident` occurs anywhere within `tokens` (including nested groups).
+pub(crate)
+fn mentions(tokens: TokenStream2, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        | TT::Ident(it) => it == *ident,
+        | TT::Group(group) => mentions(group.stream(), ident),
+        | TT::Punct(_) | TT::Literal(_) => false,
+    })
+}
+
+/// The `#[cfg(…)]` attributes among `attrs`, to be carried over to the code generated for the
+/// item they annotate (`#[cfg_attr(…)]`s are not, since they may expand to helper attributes which
+/// only make sense on the item itself).
+pub(crate)
+fn cfg_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("cfg")).collect()
+}
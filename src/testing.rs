@@ -0,0 +1,264 @@
+//! Drop-instrumentation utilities, for tests.
+//!
+//! Requires the `"testing"` Cargo feature, typically enabled only for `[dev-dependencies]`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ::drop_with_owned_fields::{
+//!     assert_custom_drop_ran, assert_drops_in_order,
+//!     drop_with_owned_fields,
+//!     testing::{DropTracker, Tracked},
+//! };
+//!
+//! #[drop_with_owned_fields(as struct FooFields, track_drops)]
+//! struct Foo {
+//!     a: Tracked<()>,
+//!     b: Tracked<()>,
+//! }
+//!
+//! #[drop_with_owned_fields]
+//! impl Drop for Foo {
+//!     fn drop(Self { a, b }: _) {
+//!         drop(b);
+//!         drop(a);
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let tracker = DropTracker::new();
+//!     let new_foo = || -> Foo {
+//!         FooFields { a: tracker.track("a", ()), b: tracker.track("b", ()) }.into()
+//!     };
+//!
+//!     drop(new_foo());
+//!     assert_custom_drop_ran!(tracker, Foo);
+//!     assert_drops_in_order!(tracker, ["b", "a"]);
+//!
+//!     let foo = new_foo();
+//!     tracker.clear();
+//!     drop(foo.destructure_fields_disabling_impl_drop());
+//!     assert_custom_drop_ran!(tracker, Foo, times = 0);
+//!     assert_drops_in_order!(tracker, ["a", "b"]);
+//! }
+//! ```
+#![allow(clippy::needless_doctest_main)]
+
+use ::core::{
+    any::type_name,
+    cell::RefCell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use ::alloc::{
+    rc::Rc,
+    vec::Vec,
+};
+
+/// An event recorded by a [`DropTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+enum DropEvent {
+    /// A [`Tracked`] value, with the given name, has been dropped.
+    Dropped(&'static str),
+
+    /// The <code>impl [DropWithOwnedFields]</code> logic of the type with the given
+    /// [`type_name()`] has run.
+    ///
+    /// Only recorded for `track_drops`-annotated types.
+    ///
+    /// [DropWithOwnedFields]: `crate::DropWithOwnedFields`
+    CustomDrop(&'static str),
+}
+
+/// A log of [`DropEvent`]s.
+///
+/// Creating one through [`DropTracker::new()`] makes it the current tracker of this thread, the
+/// one into which the `track_drops`-annotated types record their [`DropEvent::CustomDrop`]s.
+#[derive(Clone, Default)]
+pub
+struct DropTracker {
+    events: Rc<RefCell<Vec<DropEvent>>>,
+}
+
+::std::thread_local! {
+    static CURRENT: RefCell<Option<DropTracker>> = const { RefCell::new(None) };
+}
+
+impl DropTracker {
+    /// Creates a new tracker, and makes it the current one for this thread.
+    pub
+    fn new() -> Self {
+        let this = Self::default();
+        CURRENT.with_borrow_mut(|it| *it = Some(this.clone()));
+        this
+    }
+
+    /// Wraps `value` so that its drop be recorded, as a <code>[DropEvent::Dropped]\(name)</code>.
+    pub
+    fn track<T>(&self, name: &'static str, value: T) -> Tracked<T> {
+        Tracked {
+            value,
+            name,
+            tracker: self.clone(),
+        }
+    }
+
+    /// All the events recorded so far, in chronological order.
+    pub
+    fn events(&self) -> Vec<DropEvent> {
+        self.events.borrow().clone()
+    }
+
+    /// The names of the [`Tracked`] values dropped so far, in chronological order.
+    pub
+    fn dropped(&self) -> Vec<&'static str> {
+        self.events
+            .borrow()
+            .iter()
+            .filter_map(|event| match *event {
+                | DropEvent::Dropped(name) => Some(name),
+                | DropEvent::CustomDrop(_) => None,
+            })
+            .collect()
+    }
+
+    /// How many times the <code>impl [DropWithOwnedFields]</code> logic of `T` has run so far.
+    ///
+    /// [DropWithOwnedFields]: `crate::DropWithOwnedFields`
+    pub
+    fn custom_drops_of<T : ?Sized>(&self) -> usize {
+        self.events
+            .borrow()
+            .iter()
+            .filter(|&&event| event == DropEvent::CustomDrop(type_name::<T>()))
+            .count()
+    }
+
+    /// Forgets about the events recorded so far.
+    pub
+    fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+
+    fn record(&self, event: DropEvent) {
+        self.events.borrow_mut().push(event);
+    }
+}
+
+impl fmt::Debug for DropTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DropTracker").field(&*self.events.borrow()).finish()
+    }
+}
+
+pub(crate)
+fn record_custom_drop<T : ?Sized>() {
+    // `try_with`, in case we are being dropped during thread-local destruction.
+    let _ = CURRENT.try_with(|it| {
+        if let Some(tracker) = &*it.borrow() {
+            tracker.record(DropEvent::CustomDrop(type_name::<T>()));
+        }
+    });
+}
+
+/// A `T` whose drop gets recorded into a [`DropTracker`].
+///
+/// Obtained through [`DropTracker::track()`].
+pub
+struct Tracked<T> {
+    value: T,
+    name: &'static str,
+    tracker: DropTracker,
+}
+
+impl<T> Tracked<T> {
+    /// The name with which this value has been [tracked][`DropTracker::track()`].
+    pub
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.tracker.record(DropEvent::Dropped(self.name));
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T : fmt::Debug> fmt::Debug for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracked")
+            .field("name", &self.name)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// Asserts that the [`Tracked`][crate::testing::Tracked] values of a
+/// [`DropTracker`][crate::testing::DropTracker] have been dropped, so far, exactly in the given
+/// order.
+///
+/// ```rust ,ignore
+/// assert_drops_in_order!(tracker, ["b", "a"]);
+/// ```
+///
+/// See the [`testing`][crate::testing] module for an example.
+#[macro_export]
+macro_rules! assert_drops_in_order {(
+    $tracker:expr, [ $($name:expr),* $(,)? ] $(,)?
+) => ({
+    let expected: &[&'static str] = &[$($name),*];
+    ::core::assert_eq!(
+        $crate::testing::DropTracker::dropped(&$tracker),
+        expected,
+        "unexpected drop order",
+    );
+})}
+
+/// Asserts that the <code>impl [DropWithOwnedFields]</code> logic of a `track_drops`-annotated
+/// type has run exactly once (or exactly `times` times), so far, as recorded by a
+/// [`DropTracker`][crate::testing::DropTracker].
+///
+/// ```rust ,ignore
+/// assert_custom_drop_ran!(tracker, Foo);
+/// assert_custom_drop_ran!(tracker, Defused, times = 0);
+/// ```
+///
+/// See the [`testing`][crate::testing] module for an example.
+///
+/// [DropWithOwnedFields]: `crate::DropWithOwnedFields`
+#[macro_export]
+macro_rules! assert_custom_drop_ran {
+    (
+        $tracker:expr, $T:ty $(,)?
+    ) => (
+        $crate::assert_custom_drop_ran!($tracker, $T, times = 1)
+    );
+
+    (
+        $tracker:expr, $T:ty, times = $times:expr $(,)?
+    ) => ({
+        let times: usize = $times;
+        ::core::assert_eq!(
+            $crate::testing::DropTracker::custom_drops_of::<$T>(&$tracker),
+            times,
+            "unexpected amount of custom drops of `{}`",
+            ::core::any::type_name::<$T>(),
+        );
+    });
+}
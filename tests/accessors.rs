@@ -0,0 +1,72 @@
+mod module {
+    use ::drop_with_owned_fields::drop_with_owned_fields;
+
+    #[drop_with_owned_fields(as pub struct ConfigFields, accessors(pub))]
+    pub struct Config {
+        name: String,
+        r#type: u8,
+        #[drop_with_owned_fields(get)]
+        retries: u32,
+    }
+
+    #[drop_with_owned_fields]
+    impl Drop for Config {
+        fn drop(Self { .. }: _) {}
+    }
+
+    impl Config {
+        pub fn new(name: &str) -> Self {
+            ConfigFields { name: name.into(), r#type: 0, retries: 3 }.into()
+        }
+    }
+
+    #[drop_with_owned_fields(as struct MarkedFields)]
+    pub struct Marked {
+        #[drop_with_owned_fields(get, set)]
+        pub(crate) a: u8,
+        pub(crate) b: u8,
+    }
+
+    #[drop_with_owned_fields]
+    impl Drop for Marked {
+        fn drop(Self { .. }: _) {}
+    }
+
+    impl Marked {
+        pub fn new() -> Self {
+            MarkedFields { a: 0, b: 0 }.into()
+        }
+    }
+}
+
+use module::{Config, Marked};
+
+#[test]
+fn all_accessors() {
+    let mut config = Config::new("foo");
+    assert_eq!(config.name(), "foo");
+    config.name_mut().push_str("bar");
+    assert_eq!(config.set_name("baz".into()), "foobar");
+    assert_eq!(config.name(), "baz");
+
+    assert_eq!(*config.r#type(), 0);
+    *config.type_mut() = 1;
+    assert_eq!(config.set_type(2), 1);
+    assert_eq!(*config.r#type(), 2);
+}
+
+#[test]
+fn marked_field_takes_precedence() {
+    let config = Config::new("foo");
+    assert_eq!(*config.retries(), 3);
+    // No `retries_mut()` nor `set_retries()`.
+}
+
+#[test]
+fn markers_only() {
+    let mut marked = Marked::new();
+    assert_eq!(marked.set_a(1), 0);
+    assert_eq!(*marked.a(), 1);
+    // No accessors for `b`.
+    assert_eq!(marked.b, 0);
+}
@@ -0,0 +1,65 @@
+use ::core::borrow::{Borrow, BorrowMut};
+use ::drop_with_owned_fields::{drop_with_owned_fields, FieldsRef};
+
+#[drop_with_owned_fields(as struct CounterFields, as_ref, borrow)]
+#[derive(Default)]
+struct Counter {
+    count: u32,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Counter {
+    fn drop(Self { .. }: _) {}
+}
+
+#[drop_with_owned_fields(as struct NameFields, as_ref)]
+struct Name<'a> {
+    name: &'a str,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Name<'_> {
+    fn drop(Self { .. }: _) {}
+}
+
+fn increment(mut counter: impl AsMut<CounterFields>) -> u32 {
+    counter.as_mut().count += 1;
+    counter.as_mut().count
+}
+
+fn count(counter: impl Borrow<CounterFields>) -> u32 {
+    counter.borrow().count
+}
+
+#[test]
+fn as_ref_and_as_mut() {
+    let mut counter = Counter::default();
+    assert_eq!(increment(&mut counter), 1);
+    assert_eq!(AsRef::<CounterFields>::as_ref(&counter).count, 1);
+
+    let name = Name::from(NameFields { name: "foo" });
+    let fields: &NameFields<'_> = name.as_ref();
+    assert_eq!(fields.name, "foo");
+}
+
+#[test]
+fn borrow() {
+    let mut counter = Counter::default();
+    BorrowMut::<CounterFields>::borrow_mut(&mut counter).count = 2;
+    assert_eq!(count(counter), 2);
+}
+
+fn reset<T : FieldsRef>(value: &mut T)
+where
+    T::Fields : Default,
+{
+    *value.fields_mut() = <_>::default();
+}
+
+#[test]
+fn fields_ref() {
+    let mut counter = Counter::from(CounterFields { count: 3 });
+    assert_eq!(counter.fields().count, 3);
+    reset(&mut counter);
+    assert_eq!(counter.fields().count, 0);
+}
@@ -0,0 +1,47 @@
+//! Run with `--features ascii-idents` for the ASCII-only naming scheme.
+#![deny(non_ascii_idents, nonstandard_style)]
+
+use ::drop_with_owned_fields::{drop_with_owned_fields, DestructuredFieldsOf};
+
+#[drop_with_owned_fields(as _, accessors)]
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Point {
+    x: u8,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Point {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn generated_names() {
+    let point = Point::from(DestructuredFieldsOf::<Point> { x: 42 });
+    assert_eq!(*point.x(), 42);
+    assert!(point.clone() == DestructuredFieldsOf::<Point> { x: 42 });
+    let name = ::core::any::type_name::<DestructuredFieldsOf<Point>>();
+    if cfg!(feature = "ascii-idents") {
+        assert!(name.is_ascii(), "{name}");
+        assert!(name.ends_with(
+            "::_Point__drop_with_owned_fields::PointFields__drop_with_owned_fields",
+        ));
+    } else {
+        assert!(name.ends_with("::_Pointඞdrop_with_owned_fields::PointඞFields"), "{name}");
+    }
+}
+
+#[drop_with_owned_fields(as struct SegmentFields, reverse_drop_order)]
+struct Segment {
+    start: Point,
+    end: Point,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Segment {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn drop_ordered_twin() {
+    drop(Segment::from(SegmentFields { start: <_>::default(), end: <_>::default() }));
+}
@@ -0,0 +1,57 @@
+use ::drop_with_owned_fields::{drop_with_owned_fields, DestructureFields};
+use ::serde::de::{value::{Error, MapDeserializer}, Deserialize};
+use ::serde_derive::{Deserialize, Serialize};
+
+#[drop_with_owned_fields(as struct StatsFields, accessors)]
+#[derive(Clone, Default)]
+struct Stats {
+    hits: u32,
+    #[cfg(any())]
+    disabled: Vec<u8>,
+    #[cfg(not(any()))]
+    enabled: u8,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Stats {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn clone_and_default() {
+    let mut stats = Stats::default();
+    stats.hits = 1;
+    stats.enabled = 2;
+    let clone = stats.clone();
+    assert_eq!((clone.hits, clone.enabled), (1, 2));
+    assert_eq!(*clone.enabled(), 2);
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(Stats::FIELD_NAMES, ["hits", "enabled"]);
+}
+
+#[drop_with_owned_fields(as struct LabelFields)]
+#[derive(Clone, Default, Deserialize, Serialize)]
+struct Label {
+    #[cfg_attr(all(), serde(rename = "renamed"))]
+    #[cfg_attr(any(), serde(skip))]
+    name: String,
+    #[cfg(any())]
+    #[cfg_attr(all(), serde(default))]
+    disabled: String,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Label {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn cfg_attr_helper_attributes() {
+    let map = MapDeserializer::<_, Error>::new([("renamed", "label")].into_iter());
+    let label = Label::deserialize(map).unwrap();
+    assert_eq!(label.clone().name, "label");
+    assert_eq!(Label::FIELD_NAMES, ["name"]);
+}
@@ -0,0 +1,52 @@
+use ::core::cell::Cell;
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+::std::thread_local! {
+    static CLOSED: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+// Both definitions live in the same module: every item of the expansion of the enabled one carries
+// its `#[cfg]`.
+
+#[drop_with_owned_fields(as pub(crate) struct HandleFields, delegate(::core::fmt::Display))]
+#[cfg(unix)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Handle {
+    fd: i32,
+}
+
+#[drop_with_owned_fields(as pub(crate) struct HandleFields, delegate(::core::fmt::Display))]
+#[cfg(not(unix))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Handle {
+    raw: usize,
+}
+
+impl ::core::fmt::Display for HandleFields {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(Handle::KIND)
+    }
+}
+
+#[drop_with_owned_fields]
+impl Drop for Handle {
+    fn drop(Self { .. }: _) {
+        CLOSED.set(Some(Handle::KIND));
+    }
+}
+
+impl Handle {
+    #[cfg(unix)]
+    const KIND: &'static str = "fd";
+    #[cfg(not(unix))]
+    const KIND: &'static str = "raw";
+}
+
+#[test]
+fn only_the_enabled_definition() {
+    let handle = Handle::default();
+    assert!(handle.clone() == HandleFields::default());
+    assert_eq!(handle.to_string(), Handle::KIND);
+    drop(handle);
+    assert_eq!(CLOSED.get(), Some(Handle::KIND));
+}
@@ -0,0 +1,62 @@
+use ::core::ops::Not as _;
+use ::drop_with_owned_fields::{drop_with_owned_fields, DestructuredFieldsOf};
+use ::std::collections::{BTreeSet, HashMap, HashSet};
+
+#[drop_with_owned_fields(as struct VersionFields, borrow)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version<T> {
+    major: T,
+    minor: T,
+}
+
+#[drop_with_owned_fields]
+impl<T> Drop for Version<T> {
+    fn drop(Self { .. }: _) {}
+}
+
+fn v(major: u8, minor: u8) -> Version<u8> {
+    VersionFields { major, minor }.into()
+}
+
+#[test]
+fn eq() {
+    assert_eq!(v(1, 2), VersionFields { major: 1, minor: 2 });
+    assert_eq!(VersionFields { major: 1, minor: 2 }, v(1, 2));
+    assert_ne!(v(1, 2), VersionFields { major: 1, minor: 3 });
+    assert_eq!(v(1, 2), v(1, 2));
+}
+
+#[test]
+fn ord() {
+    assert!(v(1, 2) < VersionFields { major: 1, minor: 3 });
+    assert!(VersionFields { major: 2, minor: 0 } > v(1, 9));
+    let set = BTreeSet::from([v(2, 0), v(1, 0)]);
+    assert_eq!(*set.first().unwrap(), VersionFields { major: 1, minor: 0 });
+}
+
+#[test]
+fn hash_lookups() {
+    let map = HashMap::from([(v(1, 0), "one"), (v(2, 0), "two")]);
+    assert_eq!(map.get(&VersionFields { major: 2, minor: 0 }), Some(&"two"));
+    let set = HashSet::from([v(1, 0)]);
+    assert!(set.contains(&VersionFields { major: 1, minor: 0 }));
+    assert!(set.contains(&VersionFields { major: 1, minor: 1 }).not());
+}
+
+#[drop_with_owned_fields(as _)]
+#[derive(PartialEq, PartialOrd)]
+struct Sealed<'a> {
+    name: &'a str,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Sealed<'_> {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn sealed_fields() {
+    let sealed = Sealed::from(DestructuredFieldsOf::<Sealed<'_>> { name: "a" });
+    assert!(sealed == DestructuredFieldsOf::<Sealed<'_>> { name: "a" });
+    assert!(sealed < DestructuredFieldsOf::<Sealed<'_>> { name: "b" });
+}
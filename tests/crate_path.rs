@@ -0,0 +1,56 @@
+//! Using the attribute through a re-export, as a framework building on top of this crate would.
+
+mod framework {
+    pub mod dwof {
+        pub use ::drop_with_owned_fields::*;
+    }
+}
+
+use framework::dwof::{delegatable, drop_with_owned_fields, DestructureFields};
+
+#[delegatable]
+trait Greet {
+    fn greet(&self) -> String;
+}
+
+#[drop_with_owned_fields(
+    as struct GreeterFields,
+    crate = crate::framework::dwof,
+    delegate(Greet, ::core::fmt::Display),
+    accessors,
+)]
+#[derive(Clone, Default, PartialEq)]
+struct Greeter {
+    name: String,
+}
+
+impl Greet for GreeterFields {
+    fn greet(&self) -> String {
+        format!("Hello, {}!", self.name)
+    }
+}
+
+impl ::core::fmt::Display for GreeterFields {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+#[drop_with_owned_fields(crate = crate::framework::dwof)]
+impl Drop for Greeter {
+    fn drop(Self { name }: _) {
+        assert_eq!(name, "world");
+    }
+}
+
+#[test]
+fn through_a_re_export() {
+    let greeter = Greeter::from(GreeterFields { name: "world".into() });
+    assert_eq!(greeter.greet(), "Hello, world!");
+    assert_eq!(greeter.to_string(), "world");
+    assert_eq!(greeter.name(), "world");
+    assert!(greeter.clone() == GreeterFields { name: "world".into() });
+    assert_eq!(Greeter::FIELD_NAMES, ["name"]);
+    let GreeterFields { name } = Greeter::default().destructure_fields_disabling_impl_drop();
+    assert_eq!(name, "");
+}
@@ -0,0 +1,118 @@
+use ::drop_with_owned_fields::{
+    assert_drops_in_order,
+    declarative::drop_with_owned_fields,
+    testing::{DropTracker, Tracked},
+    DestructureFields,
+};
+
+mod module {
+    use super::*;
+
+    drop_with_owned_fields! {
+        /// Docs.
+        #[as pub(crate) struct LoggerFields, helper_mod = logger_helpers]
+        #[derive(Debug, PartialEq)]
+        pub(crate) struct Logger<'a, T : ::core::fmt::Display> {
+            pub(crate) out: &'a mut String,
+            pub prefix: String,
+            lines: Vec<T>,
+        }
+
+        impl Drop {
+            fn drop(Self { out, prefix, lines }: _) {
+                for line in lines {
+                    *out += &format!("{prefix}{line}\n");
+                }
+            }
+        }
+    }
+
+    impl<'a, T : ::core::fmt::Display> Logger<'a, T> {
+        pub(crate) fn new(out: &'a mut String, prefix: &str) -> Self {
+            LoggerFields { out, prefix: prefix.into(), lines: vec![] }.into()
+        }
+
+        pub(crate) fn log(&mut self, line: T) {
+            self.lines.push(line);
+        }
+    }
+}
+
+use module::{Logger, LoggerFields};
+
+mod outer {
+    pub(super) mod inner {
+        use ::drop_with_owned_fields::declarative::drop_with_owned_fields;
+
+        drop_with_owned_fields! {
+            #[as pub(super) struct LimitFields, helper_mod = limit_helpers]
+            pub(super) struct Limit {
+                pub(super) max: u8,
+            }
+        }
+
+        impl ::drop_with_owned_fields::DropWithOwnedFields for Limit {
+            fn drop(_: LimitFields) {}
+        }
+    }
+
+    // `LimitFields::into()` is as visible as `Limit` (`const`, for it to be the inherent one).
+    const LIMIT: inner::Limit = inner::LimitFields { max: 42 }.into();
+
+    pub(super) fn limit_max() -> u8 {
+        LIMIT.max
+    }
+}
+
+#[test]
+fn custom_drop() {
+    let mut out = String::new();
+    let mut logger = Logger::new(&mut out, "> ");
+    logger.log("a");
+    logger.log("b");
+    assert_eq!(logger.prefix, "> ");
+    drop(logger);
+    assert_eq!(out, "> a\n> b\n");
+}
+
+#[test]
+fn defusing() {
+    let mut out = String::new();
+    let mut logger = Logger::new(&mut out, "");
+    logger.log("a");
+    let LoggerFields { out: _, prefix, .. } = logger.destructure_fields_disabling_impl_drop();
+    assert_eq!(prefix, "");
+    assert_eq!(out, "");
+}
+
+#[test]
+fn reflection() {
+    assert_eq!(Logger::<'_, u8>::FIELD_NAMES, ["out", "prefix", "lines"]);
+}
+
+drop_with_owned_fields! {
+    #[as struct NoSugarFields, helper_mod = no_sugar_helpers]
+    struct NoSugar {
+        a: Tracked<()>,
+        b: Tracked<()>,
+    }
+}
+
+impl ::drop_with_owned_fields::DropWithOwnedFields for NoSugar {
+    fn drop(NoSugarFields { a, b }: NoSugarFields) {
+        drop(b);
+        drop(a);
+    }
+}
+
+#[test]
+fn no_sugar() {
+    let tracker = DropTracker::new();
+    drop(NoSugar::from(NoSugarFields { a: tracker.track("a", ()), b: tracker.track("b", ()) }));
+    assert_drops_in_order!(tracker, ["b", "a"]);
+}
+
+#[test]
+fn into_from_the_parent_module() {
+    assert_eq!(outer::limit_max(), 42);
+}
@@ -0,0 +1,35 @@
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+#[drop_with_owned_fields(as struct BufFields)]
+struct Buf<T, Len = usize> {
+    items: Vec<T>,
+    len: Len,
+}
+
+#[drop_with_owned_fields]
+impl<T, Len> Drop for Buf<T, Len> {
+    fn drop(Self { .. }: _) {}
+}
+
+#[drop_with_owned_fields(as struct ArrFields)]
+struct Arr<const N: usize = 4> {
+    bytes: [u8; N],
+}
+
+#[drop_with_owned_fields]
+impl<const N: usize> Drop for Arr<N> {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn defaults_are_preserved() {
+    // `Buf<u8>` is `Buf<u8, usize>`.
+    let buf: Buf<u8> = BufFields { items: vec![1, 2], len: 2_usize }.into();
+    let fields: &BufFields<u8> = &buf;
+    assert_eq!(fields.len, fields.items.len());
+
+    let arr: Arr = ArrFields { bytes: [0; 4] }.into();
+    assert_eq!(arr.bytes.len(), 4);
+    let arr: Arr<2> = ArrFields { bytes: [0; 2] }.into();
+    assert_eq!(arr.bytes.len(), 2);
+}
@@ -0,0 +1,201 @@
+extern crate alloc;
+
+use ::drop_with_owned_fields::{delegatable, drop_with_owned_fields};
+use ::std::io::{Read, Write};
+
+#[drop_with_owned_fields(
+    as struct PipeFields,
+    // Bare names of well-known traits need not be in scope (a bare `Write` being that of `io`).
+    delegate(Display, Read, Write, Iterator, AsRef<[u8]>, ::alloc::fmt::Write),
+)]
+struct Pipe {
+    buffer: Vec<u8>,
+    read_pos: usize,
+}
+
+impl ::core::fmt::Display for PipeFields {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.buffer[self.read_pos ..]))
+    }
+}
+
+impl Read for PipeFields {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        let n = (&self.buffer[self.read_pos ..]).read(buf)?;
+        self.read_pos += n;
+        Ok(n)
+    }
+}
+
+impl Write for PipeFields {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> ::std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl ::core::fmt::Write for PipeFields {
+    fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+        self.buffer.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Iterator for PipeFields {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.buffer.get(self.read_pos)?;
+        self.read_pos += 1;
+        Some(byte)
+    }
+}
+
+impl AsRef<[u8]> for PipeFields {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+#[drop_with_owned_fields]
+impl Drop for Pipe {
+    fn drop(Self { .. }: _) {}
+}
+
+fn pipe() -> Pipe {
+    PipeFields { buffer: vec![], read_pos: 0 }.into()
+}
+
+#[test]
+fn well_known_traits() {
+    let mut pipe = pipe();
+    pipe.write_all(b"hello, ").unwrap();
+    ::core::fmt::Write::write_str(&mut pipe, "world").unwrap();
+    assert_eq!(AsRef::<[u8]>::as_ref(&pipe), b"hello, world");
+    assert_eq!(pipe.next(), Some(b'h'));
+    let mut buf = [0; 4];
+    pipe.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ello");
+    assert_eq!(pipe.to_string(), ", world");
+    assert_eq!(Iterator::by_ref(&mut pipe).collect::<Vec<_>>(), b", world");
+}
+
+mod shapes {
+    use super::*;
+
+    #[delegatable]
+    pub trait Shape {
+        type Unit;
+        const SIDES: usize;
+
+        fn area(&self) -> f64;
+
+        fn scale(&mut self, factor: f64);
+
+        fn describe(&self, name: &str) -> String {
+            format!("{name}: {} sides", Self::SIDES)
+        }
+
+        fn into_sides(self) -> usize
+        where
+            Self : Sized,
+        {
+            Self::SIDES
+        }
+
+        fn unit() -> Self::Unit;
+    }
+}
+
+#[drop_with_owned_fields(as struct SquareFields, delegate(shapes::Shape))]
+struct Square<T> {
+    side: T,
+}
+
+impl shapes::Shape for SquareFields<f64> {
+    type Unit = &'static str;
+    const SIDES: usize = 4;
+
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.side *= factor;
+    }
+
+    fn describe(&self, name: &str) -> String {
+        format!("{name}: square of side {}", self.side)
+    }
+
+    fn unit() -> &'static str {
+        "m"
+    }
+}
+
+#[drop_with_owned_fields]
+impl<T> Drop for Square<T> {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn delegatable_trait() {
+    use shapes::Shape;
+
+    let mut square = Square::from(SquareFields { side: 2.0 });
+    square.scale(1.5);
+    assert_eq!(square.area(), 9.0);
+    assert_eq!(square.describe("sq"), "sq: square of side 3");
+    assert_eq!(<Square<f64> as Shape>::SIDES, 4);
+    assert_eq!(Square::<f64>::unit(), "m");
+    // The default implementation is kept for by-value receivers.
+    assert_eq!(square.into_sides(), 4);
+}
+
+/// Traits of the caller sharing the name (and module name) of well-known ones.
+mod fmt {
+    use super::*;
+
+    #[delegatable]
+    pub trait Display {
+        fn display(&self) -> String;
+    }
+
+    #[delegatable]
+    pub trait Write {
+        fn write(&mut self, s: &str);
+    }
+}
+use fmt::Display;
+
+#[drop_with_owned_fields(as struct LabelFields, delegate(self::Display, fmt::Write))]
+struct Label {
+    text: String,
+}
+
+impl Display for LabelFields {
+    fn display(&self) -> String {
+        format!("<{}>", self.text)
+    }
+}
+
+impl fmt::Write for LabelFields {
+    fn write(&mut self, s: &str) {
+        self.text += s;
+    }
+}
+
+#[drop_with_owned_fields]
+impl Drop for Label {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn homonyms_of_well_known_traits() {
+    let mut label = Label::from(LabelFields { text: "hi".into() });
+    fmt::Write::write(&mut label, "!");
+    assert_eq!(label.display(), "<hi!>");
+}
@@ -0,0 +1,46 @@
+use ::drop_with_owned_fields::{
+    assert_custom_drop_ran, assert_drops_in_order,
+    drop_with_owned_fields,
+    testing::{DropTracker, Tracked},
+};
+
+#[drop_with_owned_fields(as struct GuardFields, disarmable, track_drops)]
+struct Guard {
+    field: Tracked<()>,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Guard {
+    fn drop(Self { .. }: _) {}
+}
+
+fn new_guard(tracker: &DropTracker) -> Guard {
+    GuardFields { field: tracker.track("field", ()) }.into()
+}
+
+#[test]
+fn disarm_and_rearm() {
+    let tracker = DropTracker::new();
+    let mut guards = vec![new_guard(&tracker), new_guard(&tracker), new_guard(&tracker)];
+    guards[0].disarm();
+    guards[1].disarm();
+    guards[1].rearm();
+    assert_eq!(
+        guards.iter().map(Guard::is_armed).collect::<Vec<_>>(),
+        [false, true, true],
+    );
+    drop(guards);
+    assert_custom_drop_ran!(tracker, Guard, times = 2);
+    assert_drops_in_order!(tracker, ["field", "field", "field"]);
+}
+
+#[test]
+fn destructure_disarmed() {
+    let tracker = DropTracker::new();
+    let mut guard = new_guard(&tracker);
+    guard.disarm();
+    let GuardFields { field } = guard.destructure_fields_disabling_impl_drop();
+    drop(field);
+    assert_custom_drop_ran!(tracker, Guard, times = 0);
+    assert_drops_in_order!(tracker, ["field"]);
+}
@@ -0,0 +1,132 @@
+use ::core::cell::RefCell;
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+::std::thread_local! {
+    static DROPPED: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+struct Logged(&'static str);
+
+impl Drop for Logged {
+    fn drop(&mut self) {
+        DROPPED.with_borrow_mut(|it| it.push(self.0));
+    }
+}
+
+fn dropped() -> Vec<&'static str> {
+    DROPPED.take()
+}
+
+#[drop_with_owned_fields(as struct ExplicitFields, drop_order(c, a))]
+struct Explicit {
+    a: Logged,
+    #[drop_with_owned_fields(drop_last)]
+    b: Logged,
+    c: Logged,
+    d: Logged,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Explicit {
+    fn drop(Self { d, .. }: _) {
+        drop(d);
+    }
+}
+
+#[drop_with_owned_fields(as struct ReverseFields, reverse_drop_order)]
+struct Reverse {
+    a: Logged,
+    b: Logged,
+    c: Logged,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Reverse {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn explicit() {
+    drop(Explicit::from(ExplicitFields {
+        a: Logged("a"),
+        b: Logged("b"),
+        c: Logged("c"),
+        d: Logged("d"),
+    }));
+    assert_eq!(dropped(), ["d", "c", "a", "b"]);
+}
+
+#[test]
+fn reverse() {
+    drop(Reverse::from(ReverseFields {
+        a: Logged("a"),
+        b: Logged("b"),
+        c: Logged("c"),
+    }));
+    assert_eq!(dropped(), ["c", "b", "a"]);
+}
+
+/// The drop order only concerns the fields left over in the drop logic: the companion `struct`
+/// itself is declared as written.
+#[test]
+fn disabling_impl_drop() {
+    let reverse = Reverse::from(ReverseFields {
+        a: Logged("a"),
+        b: Logged("b"),
+        c: Logged("c"),
+    });
+    drop(reverse.destructure_fields_disabling_impl_drop());
+    assert_eq!(dropped(), ["a", "b", "c"]);
+}
+
+#[drop_with_owned_fields(as struct VersionFields, reverse_drop_order)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u8,
+    minor: u8,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Version {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn declaration_order_is_kept() {
+    let older = Version::from(VersionFields { major: 1, minor: 9 });
+    let newer = Version::from(VersionFields { major: 2, minor: 0 });
+    assert!(older < newer);
+    assert_eq!(older.cmp(&newer), (*older).cmp(&*newer));
+    assert_eq!(format!("{:?}", *older), "VersionFields { major: 1, minor: 9 }");
+}
+
+#[drop_with_owned_fields(as struct DisarmableFields, disarmable, drop_order(c, a))]
+struct Disarmable {
+    a: Logged,
+    b: Logged,
+    c: Logged,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Disarmable {
+    fn drop(Self { .. }: _) {
+        DROPPED.with_borrow_mut(|it| it.push("custom"));
+    }
+}
+
+/// Disarming only skips the drop logic: the fields are still dropped in the configured order.
+#[test]
+fn disarmed() {
+    let new_disarmable = || Disarmable::from(DisarmableFields {
+        a: Logged("a"),
+        b: Logged("b"),
+        c: Logged("c"),
+    });
+    drop(new_disarmable());
+    assert_eq!(dropped(), ["custom", "c", "a", "b"]);
+
+    let mut disarmable = new_disarmable();
+    disarmable.disarm();
+    drop(disarmable);
+    assert_eq!(dropped(), ["c", "a", "b"]);
+}
@@ -0,0 +1,36 @@
+use ::core::cell::Cell;
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+::std::thread_local! {
+    static CALLED: Cell<usize> = const { Cell::new(0) };
+}
+
+#[drop_with_owned_fields(as struct HookFields)]
+struct Hook<'a, F : Fn(&'a str) -> usize, const N: usize> {
+    f: F,
+    arg: &'a str,
+}
+
+#[drop_with_owned_fields]
+/// Docs, and then a where clause with `-> <…>` shenanigans.
+impl<'a, F, const N: usize> Drop for Hook<'a, F, N>
+where
+    F : Fn(&'a str) -> usize,
+{
+    #[inline]
+    fn drop(
+        #[allow(unused_variables)]
+        Self { f, arg }: _,
+    ) {
+        for _ in 0 .. N {
+            CALLED.set(CALLED.get() + f(arg));
+        }
+    }
+}
+
+#[test]
+fn generics_and_where_clauses() {
+    CALLED.set(0);
+    drop(Hook::<_, 2>::from(HookFields { f: str::len, arg: "abc" }));
+    assert_eq!(CALLED.get(), 6);
+}
@@ -0,0 +1,62 @@
+//! The expansions contain no `unsafe` blocks, so as to be usable within
+//! `#![forbid(unsafe_code)]` crates.
+#![forbid(unsafe_code)]
+
+use ::drop_with_owned_fields::{
+    assert_custom_drop_ran, assert_drops_in_order,
+    declarative, drop_with_owned_fields,
+    testing::{DropTracker, Tracked},
+};
+
+#[drop_with_owned_fields(
+    as struct GuardFields,
+    disarmable,
+    on_panic = abort,
+    accessors,
+    track_drops,
+)]
+#[derive(Clone, Default, Debug)]
+struct Guard<T> {
+    value: T,
+}
+
+#[drop_with_owned_fields]
+impl<T> Drop for Guard<T> {
+    fn drop(Self { .. }: _) {}
+}
+
+declarative::drop_with_owned_fields! {
+    #[as struct CounterFields, helper_mod = counter_helpers]
+    struct Counter {
+        count: Tracked<u32>,
+        step: Tracked<u32>,
+    }
+
+    impl Drop {
+        fn drop(Self { count, step }: _) {
+            drop(step);
+            drop(count);
+        }
+    }
+}
+
+#[test]
+fn drop_and_destructure() {
+    let tracker = DropTracker::new();
+    let new_counter = |count| -> Counter {
+        CounterFields { count: tracker.track("count", count), step: tracker.track("step", 1) }
+            .into()
+    };
+    drop(Guard::from(GuardFields { value: 42 }));
+    drop(new_counter(0));
+    assert_custom_drop_ran!(tracker, Guard<i32>);
+    assert_drops_in_order!(tracker, ["step", "count"]);
+
+    tracker.clear();
+    let GuardFields { value } = Guard::<u8>::default().destructure_fields_disabling_impl_drop();
+    let CounterFields { count, step } = new_counter(1).destructure_fields_disabling_impl_drop();
+    assert_eq!((value, *count + *step), (0, 2));
+    drop((count, step));
+    assert_custom_drop_ran!(tracker, Guard<u8>, times = 0);
+    assert_drops_in_order!(tracker, ["count", "step"]);
+}
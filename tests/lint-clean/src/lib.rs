@@ -0,0 +1,110 @@
+//! The expansion is to be lint-clean, even under strict lint configurations.
+#![deny(missing_docs, unreachable_pub)]
+#![deny(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+/// Docs.
+pub mod api {
+    use ::drop_with_owned_fields::drop_with_owned_fields;
+
+    /// A connection.
+    #[drop_with_owned_fields(
+        as pub struct ConnectionFields,
+        disarmable,
+        accessors,
+        delegate(::core::fmt::Display),
+        borrow,
+    )]
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Connection<T> {
+        /// The address.
+        pub addr: String,
+        /// The payload.
+        pub payload: T,
+    }
+
+    impl<T> ::core::fmt::Display for ConnectionFields<T> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.write_str(&self.addr)
+        }
+    }
+
+    #[drop_with_owned_fields]
+    impl<T> Drop for Connection<T> {
+        fn drop(Self { .. }: _) {}
+    }
+
+    /// Private to the crate.
+    #[drop_with_owned_fields(as _, accessors)]
+    #[derive(Default)]
+    pub(crate) struct Internal {
+        pub(crate) id: u32,
+        secret: u32,
+    }
+
+    #[drop_with_owned_fields]
+    impl Drop for Internal {
+        fn drop(Self { .. }: _) {}
+    }
+
+    /// Uses the crate-private type.
+    #[must_use]
+    pub fn internal() -> (u32, u32) {
+        let internal = Internal::default();
+        (*internal.id(), *internal.secret())
+    }
+}
+
+/// With an inferred companion `struct`.
+pub mod inferred {
+    use ::drop_with_owned_fields::{drop_with_owned_fields, DestructuredFieldsOf};
+
+    /// A pair.
+    #[drop_with_owned_fields(as _, reverse_drop_order, on_panic = abort)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Pair {
+        /// The first element.
+        pub a: u8,
+        b: u8,
+    }
+
+    #[drop_with_owned_fields]
+    impl Drop for Pair {
+        fn drop(Self { a: _, b: _ }: _) {}
+    }
+
+    /// Constructs a [`Pair`].
+    #[must_use]
+    pub fn pair(a: u8, b: u8) -> Pair {
+        DestructuredFieldsOf::<Pair> { a, b }.into()
+    }
+}
+
+mod private {
+    use ::drop_with_owned_fields::drop_with_owned_fields;
+
+    /// Re-exported from a private module.
+    #[drop_with_owned_fields(as pub struct ReExportedFields, accessors(pub))]
+    pub struct ReExported<'a> {
+        name: &'a str,
+    }
+
+    #[drop_with_owned_fields]
+    impl Drop for ReExported<'_> {
+        fn drop(Self { .. }: _) {}
+    }
+}
+pub use private::{ReExported, ReExportedFields};
+
+::drop_with_owned_fields::declarative::drop_with_owned_fields! {
+    /// Through the `declarative` front-end.
+    #[as pub struct DeclarativeFields, helper_mod = declarative_helpers]
+    pub struct Declarative {
+        /// The value.
+        pub value: u8,
+    }
+
+    impl Drop {
+        fn drop(Self { .. }: _) {}
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "dropck-eyepatch")]
+
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+#[drop_with_owned_fields(as struct MyVecFields, unsafe(may_dangle(T)))]
+struct MyVec<T> {
+    items: Vec<T>,
+}
+
+#[drop_with_owned_fields]
+impl<T> Drop for MyVec<T> {
+    fn drop(Self { items }: _) {
+        drop(items);
+    }
+}
+
+#[test]
+fn can_outlive_borrowed_data() {
+    let mut v = MyVec::from(MyVecFields { items: vec![] });
+    let s = String::from("dangling upon drop");
+    v.items.push(&s);
+    assert_eq!(v.items.len(), 1);
+    // `s` is dropped before `v` here.
+}
@@ -0,0 +1,44 @@
+//! An upstream crate exposing a `#[non_exhaustive]` type.
+
+use ::core::sync::atomic::{AtomicUsize, Ordering};
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+/// How many `Config`s have been dropped.
+pub static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// A configuration, which may grow more fields in the future.
+#[drop_with_owned_fields(as pub struct ConfigFields)]
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// The name.
+    pub name: String,
+    /// The amount of retries.
+    pub retries: u8,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Config {
+    fn drop(Self { name, retries: _ }: _) {
+        drop(name);
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Config {
+    /// Constructs a `Config`, as only this crate may.
+    #[must_use]
+    pub fn new(name: &str, retries: u8) -> Self {
+        ConfigFields {
+            name: name.to_owned(),
+            retries,
+        }
+        .into()
+    }
+
+    /// Disarms the drop logic, handing out the fields instead.
+    #[must_use]
+    pub fn into_fields(self) -> ConfigFields {
+        self.destructure_fields_disabling_impl_drop()
+    }
+}
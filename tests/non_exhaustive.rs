@@ -0,0 +1,29 @@
+//! A downstream crate may read the fields of an upstream `#[non_exhaustive]` type, and drop it
+//! (running its drop logic), but not construct it: see `src/compile_fail_tests.md` for the latter.
+
+use ::core::sync::atomic::Ordering;
+use ::drop_with_owned_fields_non_exhaustive::{Config, ConfigFields, DROPS};
+
+#[test]
+fn read_and_drop_downstream() {
+    let config = Config::new("upstream", 3);
+    assert_eq!(config.name, "upstream");
+    assert_eq!(config.retries, 3);
+
+    // Upstream-provided constructors remain usable.
+    let clone = config.clone();
+    let default = Config::default();
+    assert_eq!(clone.name, "upstream");
+    assert_eq!(default.retries, 0);
+
+    let before = DROPS.load(Ordering::SeqCst);
+    drop(config);
+    drop(clone);
+    drop(default);
+    assert_eq!(DROPS.load(Ordering::SeqCst), before + 3);
+
+    // Destructuring is not constructing.
+    let ConfigFields { name, .. } = Config::new("fields", 0).into_fields();
+    assert_eq!(name, "fields");
+    assert_eq!(DROPS.load(Ordering::SeqCst), before + 3);
+}
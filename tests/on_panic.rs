@@ -0,0 +1,96 @@
+use ::core::ops::Not as _;
+use ::std::{
+    env,
+    panic,
+    process::Command,
+    sync::Mutex,
+};
+use ::drop_with_owned_fields::{drop_with_owned_fields, on_panic};
+
+struct Field<'r>(&'r Mutex<Vec<String>>);
+
+impl Drop for Field<'_> {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().push("field dropped".into());
+    }
+}
+
+#[drop_with_owned_fields(as struct CatchFields, on_panic = catch)]
+struct Catch<'r> {
+    field: Field<'r>,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Catch<'_> {
+    fn drop(Self { .. }: _) {
+        panic!("caught");
+    }
+}
+
+#[test]
+fn catch() {
+    static LOG: Mutex<Vec<String>> = Mutex::new(vec![]);
+    on_panic::set_hook(Box::new(|caught| {
+        LOG.lock().unwrap().push(format!(
+            "{}: {}",
+            caught.type_name(),
+            caught.message().unwrap(),
+        ));
+    }));
+    // Even while unwinding, there is no double panic.
+    let caught_outer = panic::catch_unwind(|| {
+        let _catch = Catch::from(CatchFields { field: Field(&LOG) });
+        panic!("outer");
+    });
+    assert!(caught_outer.is_err());
+    drop(Catch::from(CatchFields { field: Field(&LOG) }));
+    let _ = on_panic::take_hook();
+    assert_eq!(*LOG.lock().unwrap(), [
+        "field dropped",
+        "Catch: caught",
+        "field dropped",
+        "Catch: caught",
+    ]);
+
+    // The hook is called without holding on to the lock: it may thus replace itself.
+    LOG.lock().unwrap().clear();
+    on_panic::set_hook(Box::new(|_| {
+        on_panic::set_hook(Box::new(|_| LOG.lock().unwrap().push("replaced".into())));
+    }));
+    drop(Catch::from(CatchFields { field: Field(&LOG) }));
+    drop(Catch::from(CatchFields { field: Field(&LOG) }));
+    let _ = on_panic::take_hook();
+    assert_eq!(*LOG.lock().unwrap(), ["field dropped", "field dropped", "replaced"]);
+}
+
+#[drop_with_owned_fields(as struct AbortFields, on_panic = abort)]
+struct Abort {}
+
+#[drop_with_owned_fields]
+impl Drop for Abort {
+    fn drop(Self {}: _) {
+        panic!("aborted");
+    }
+}
+
+#[test]
+fn abort_child() {
+    if env::var_os("DROP_WITH_OWNED_FIELDS_ABORT_CHILD").is_some() {
+        drop(Abort::from(AbortFields {}));
+    }
+}
+
+#[test]
+fn abort() {
+    let output =
+        Command::new(env::current_exe().unwrap())
+            .args(["--exact", "abort_child", "--nocapture"])
+            .env("DROP_WITH_OWNED_FIELDS_ABORT_CHILD", "1")
+            .output()
+            .unwrap()
+    ;
+    assert!(output.status.success().not());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("aborted"), "{stderr}");
+    assert!(stderr.contains("the `DropWithOwnedFields` logic of `Abort` panicked: aborting"), "{stderr}");
+}
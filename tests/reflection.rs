@@ -0,0 +1,33 @@
+use ::core::any::type_name;
+use ::drop_with_owned_fields::{drop_with_owned_fields, DestructureFields, VisitFieldMut};
+
+#[drop_with_owned_fields(as struct PairFields)]
+struct Pair(u8, u8);
+
+#[drop_with_owned_fields]
+impl Drop for Pair {
+    fn drop(Self { .. }: _) {}
+}
+
+#[test]
+fn tuple_struct() {
+    /// Records the name, type, and address of each visited field.
+    struct Record(Vec<(&'static str, &'static str, *const ())>);
+    impl VisitFieldMut for Record {
+        fn visit_field_mut<T : ?Sized>(&mut self, name: &'static str, field: &mut T) {
+            self.0.push((name, type_name::<T>(), (field as *mut T).cast::<()>().cast_const()));
+        }
+    }
+
+    assert_eq!(Pair::FIELD_NAMES, ["0", "1"]);
+    let mut pair: Pair = PairFields(4, 2).into();
+    let mut record = Record(vec![]);
+    pair.for_each_field_mut(&mut record);
+    // The visited fields are the very ones reachable through `Deref`.
+    assert_eq!(record.0, [
+        ("0", "u8", <*const u8>::cast(&pair.0)),
+        ("1", "u8", <*const u8>::cast(&pair.1)),
+    ]);
+    let PairFields(a, b) = pair.destructure_fields_disabling_impl_drop();
+    assert_eq!((a, b), (4, 2));
+}
@@ -0,0 +1,78 @@
+use ::drop_with_owned_fields::{
+    assert_custom_drop_ran,
+    drop_with_owned_fields,
+    testing::DropTracker,
+};
+
+#[drop_with_owned_fields(as struct NodeFields, track_drops)]
+struct Node<T> {
+    value: T,
+    next: Option<Box<Self>>,
+    children: Vec<(Self, <Self as Trait>::Assoc)>,
+}
+
+trait Trait {
+    type Assoc;
+}
+
+impl<T> Trait for Node<T> {
+    type Assoc = ();
+}
+
+#[drop_with_owned_fields]
+impl<T> Drop for Node<T> {
+    fn drop(Self { .. }: _) {}
+}
+
+fn node<T>(value: T, next: Option<Node<T>>) -> Node<T> {
+    NodeFields { value, next: next.map(Box::new), children: vec![] }.into()
+}
+
+#[test]
+fn recursive_fields_keep_the_drop_logic() {
+    let tracker = DropTracker::new();
+    let mut list = node(1, Some(node(2, Some(node(3, None)))));
+    // The `next` field is a `Box<Node>`, not a `Box<NodeFields>`.
+    let next: &Option<Box<Node<i32>>> = &list.next;
+    assert_eq!(next.as_ref().unwrap().value, 2);
+    list.children.push((node(4, None), ()));
+    drop(list);
+    assert_custom_drop_ran!(tracker, Node<i32>, times = 4);
+}
+
+trait Visitor<N> {
+    fn visit(&self, node: &N) -> usize;
+}
+
+/// `V : Visitor<Tree<V>>`, not `V : Visitor<TreeFields<V>>`.
+#[drop_with_owned_fields(as struct TreeFields)]
+struct Tree<V>
+where
+    V : Visitor<Self>,
+{
+    visitor: V,
+    children: Vec<Self>,
+}
+
+#[drop_with_owned_fields]
+impl<V> Drop for Tree<V>
+where
+    V : Visitor<Tree<V>>,
+{
+    fn drop(Self { .. }: _) {}
+}
+
+struct CountNodes;
+
+impl Visitor<Tree<CountNodes>> for CountNodes {
+    fn visit(&self, node: &Tree<CountNodes>) -> usize {
+        1 + node.children.iter().map(|child| self.visit(child)).sum::<usize>()
+    }
+}
+
+#[test]
+fn where_clauses() {
+    let leaf = || Tree::from(TreeFields { visitor: CountNodes, children: vec![] });
+    let tree = Tree::from(TreeFields { visitor: CountNodes, children: vec![leaf(), leaf()] });
+    assert_eq!(tree.visitor.visit(&tree), 3);
+}
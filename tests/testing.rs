@@ -0,0 +1,58 @@
+use ::drop_with_owned_fields::{
+    assert_custom_drop_ran, assert_drops_in_order,
+    drop_with_owned_fields,
+    testing::{DropEvent, DropTracker, Tracked},
+};
+
+#[drop_with_owned_fields(as struct GuardFields, disarmable, track_drops)]
+struct Guard {
+    first: Tracked<u8>,
+    second: Tracked<u8>,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Guard {
+    fn drop(Self { second, .. }: _) {
+        drop(second);
+    }
+}
+
+fn new_guard(tracker: &DropTracker) -> Guard {
+    GuardFields {
+        first: tracker.track("first", 1),
+        second: tracker.track("second", 2),
+    }
+    .into()
+}
+
+#[test]
+fn custom_drop() {
+    let tracker = DropTracker::new();
+    let guard = new_guard(&tracker);
+    assert_eq!(*guard.first + *guard.second, 3);
+    drop(guard);
+    assert_custom_drop_ran!(tracker, Guard);
+    assert_drops_in_order!(tracker, ["second", "first"]);
+    assert_eq!(tracker.events(), [
+        DropEvent::CustomDrop(::core::any::type_name::<Guard>()),
+        DropEvent::Dropped("second"),
+        DropEvent::Dropped("first"),
+    ]);
+}
+
+#[test]
+fn disarmed() {
+    let tracker = DropTracker::new();
+    let mut guard = new_guard(&tracker);
+    guard.disarm();
+    drop(guard);
+    assert_custom_drop_ran!(tracker, Guard, times = 0);
+    assert_drops_in_order!(tracker, ["first", "second"]);
+}
+
+#[test]
+fn nothing_dropped() {
+    let tracker = DropTracker::new();
+    let _guard = new_guard(&tracker);
+    assert_drops_in_order!(tracker, []);
+}
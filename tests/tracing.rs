@@ -0,0 +1,112 @@
+use ::std::{
+    fmt,
+    panic,
+    sync::{Arc, Mutex},
+};
+use ::drop_with_owned_fields::drop_with_owned_fields;
+use ::tracing::{
+    field::{Field, Visit},
+    span,
+    Event, Metadata, Subscriber,
+};
+
+/// Logs `"span <name> kind=<kind>"` and `"event <level> <message>"` lines.
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<String>>>);
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 += &format!(" {value:?}");
+        } else {
+            self.0 += &format!(" {}={value:?}", field.name());
+        }
+    }
+}
+
+impl Subscriber for Log {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields(format!("span {}", span.metadata().name()));
+        span.record(&mut fields);
+        let mut log = self.0.lock().unwrap();
+        log.push(fields.0);
+        span::Id::from_u64(log.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(format!("event {}", event.metadata().level()));
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[drop_with_owned_fields(as struct GuardFields, trace(level = "debug"))]
+struct Guard {
+    panics: bool,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Guard {
+    fn drop(Self { panics }: _) {
+        ::tracing::info!("custom drop");
+        if panics {
+            panic!("custom drop panicked");
+        }
+    }
+}
+
+fn traced(f: impl FnOnce()) -> Vec<String> {
+    let log = Log::default();
+    ::tracing::subscriber::with_default(log.clone(), f);
+    let ret = log.0.lock().unwrap().clone();
+    ret
+}
+
+#[test]
+fn implicit() {
+    let log = traced(|| drop(Guard::from(GuardFields { panics: false })));
+    assert_eq!(log, [
+        r#"span Guard kind="implicit""#,
+        "event INFO custom drop",
+    ]);
+}
+
+#[test]
+fn unwinding() {
+    let log = traced(|| {
+        let _ = panic::catch_unwind(|| {
+            let _guard = Guard::from(GuardFields { panics: false });
+            panic!();
+        });
+    });
+    assert_eq!(log, [
+        r#"span Guard kind="unwinding""#,
+        "event INFO custom drop",
+    ]);
+}
+
+#[test]
+fn escaping_panic() {
+    let log = traced(|| {
+        let _ = panic::catch_unwind(|| drop(Guard::from(GuardFields { panics: true })));
+    });
+    assert_eq!(log, [
+        r#"span Guard kind="implicit""#,
+        "event INFO custom drop",
+        "event ERROR panic escaped the `DropWithOwnedFields` logic",
+    ]);
+}
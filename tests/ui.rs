@@ -0,0 +1,58 @@
+//! Both front-ends, the `#[drop_with_owned_fields]` sugar and the `drop_with_owned_fields!`
+//! `macro_rules!`, are to reject the very same ill-formed `impl Drop` with the very same message.
+//!
+//! `tests/ui/{sugar,declarative}/` thus hold pairs of same-named cases, whose `.stderr` snapshots
+//! are checked against `rustc` by the `ui-tests`-gated test (snapshots being `rustc`-version
+//! dependent), and against each other, always. `tests/ui/delegate/` holds the misuses of the
+//! `delegate(…)` arg, which the `macro_rules!` front-end does not support.
+
+use ::std::{fs, path::Path};
+
+#[cfg(feature = "ui-tests")]
+#[test]
+fn ui() {
+    let t = ::trybuild::TestCases::new();
+    t.compile_fail("tests/ui/sugar/*.rs");
+    t.compile_fail("tests/ui/declarative/*.rs");
+    t.compile_fail("tests/ui/delegate/*.rs");
+}
+
+/// The message of the first `error: …` line, stripped of the front-end-specific prefix.
+fn error_message(stderr_path: &Path) -> String {
+    let stderr = fs::read_to_string(stderr_path).unwrap_or_else(|err| {
+        panic!("missing `{}` snapshot: {err}", stderr_path.display())
+    });
+    let error = stderr.lines().find_map(|line| line.strip_prefix("error: ")).unwrap_or_else(|| {
+        panic!("no `error: …` line in `{}`", stderr_path.display())
+    });
+    error
+        .strip_prefix("`#[drop_with_owned_fields::drop_with_owned_fields]`: ")
+        .or_else(|| error.strip_prefix("`drop_with_owned_fields!`: "))
+        .unwrap_or_else(|| panic!("unprefixed error in `{}`: {error}", stderr_path.display()))
+        .to_owned()
+}
+
+#[test]
+fn both_front_ends_agree() {
+    let ui = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ui");
+    let mut cases = fs::read_dir(ui.join("sugar")).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| path.file_stem().unwrap().to_owned())
+        .collect::<Vec<_>>()
+    ;
+    cases.sort();
+    assert!(!cases.is_empty());
+    for case in cases {
+        let stderr = Path::new(&case).with_extension("stderr");
+        assert!(
+            ui.join("declarative").join(&case).with_extension("rs").exists(),
+            "missing `declarative` counterpart of `{}`", case.to_string_lossy(),
+        );
+        assert_eq!(
+            error_message(&ui.join("sugar").join(&stderr)),
+            error_message(&ui.join("declarative").join(&stderr)),
+            "diagnostics mismatch for `{}`", case.to_string_lossy(),
+        );
+    }
+}
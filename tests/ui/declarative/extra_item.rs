@@ -0,0 +1,15 @@
+use ::drop_with_owned_fields::declarative::drop_with_owned_fields;
+
+drop_with_owned_fields! {
+    #[as struct FooFields, helper_mod = foo_helpers]
+    struct Foo {
+        a: u8,
+    }
+
+    impl Drop {
+        fn drop(Self { a }: _) {}
+        const EXTRA: () = ();
+    }
+}
+
+fn main() {}
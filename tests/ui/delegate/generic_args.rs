@@ -0,0 +1,13 @@
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+#[drop_with_owned_fields(as struct BufferFields, delegate(std::convert::AsRef<[u8]>))]
+struct Buffer {
+    bytes: Vec<u8>,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Buffer {
+    fn drop(Self { .. }: _) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use ::drop_with_owned_fields::drop_with_owned_fields;
+
+#[drop_with_owned_fields(as struct FooFields)]
+struct Foo {
+    a: u8,
+}
+
+#[drop_with_owned_fields]
+impl Drop for Foo {
+    fn drop(Self { a }: _) {}
+    const EXTRA: () = ();
+}
+
+fn main() {}
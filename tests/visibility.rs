@@ -0,0 +1,107 @@
+//! `pub(in …)` visibilities, on the `struct` and on its fields, across nested modules.
+
+pub mod net {
+    pub mod tcp {
+        use ::drop_with_owned_fields::drop_with_owned_fields;
+
+        #[drop_with_owned_fields(as pub(in crate::net) struct SocketFields, accessors)]
+        pub(in crate::net) struct Socket {
+            pub(in crate::net) port: u16,
+            pub(super) host: String,
+            pub(self) retries: u8,
+            pub(in self) timeout: u32,
+        }
+
+        #[drop_with_owned_fields]
+        impl Drop for Socket {
+            fn drop(Self { .. }: _) {}
+        }
+
+        pub mod conn {
+            use ::drop_with_owned_fields::drop_with_owned_fields;
+
+            #[drop_with_owned_fields(as pub(in super::super) struct ConnFields)]
+            pub(in super::super) struct Conn {
+                pub(in super::super) id: u32,
+                pub(in super) tag: &'static str,
+            }
+
+            #[drop_with_owned_fields]
+            impl Drop for Conn {
+                fn drop(Self { .. }: _) {}
+            }
+
+            pub(super) fn tag(conn: &Conn) -> &'static str {
+                conn.tag
+            }
+        }
+
+        pub(in crate::net) fn socket() -> Socket {
+            SocketFields {
+                port: 80,
+                host: "localhost".into(),
+                retries: 3,
+                timeout: 30,
+            }.into()
+        }
+
+        pub(in crate::net) fn retries(socket: &Socket) -> (u8, u32) {
+            (socket.retries, *socket.timeout())
+        }
+
+        pub(in crate::net) fn conn(id: u32) -> conn::Conn {
+            conn::Conn::from(conn::ConnFields { id, tag: "" })
+        }
+
+        pub(in crate::net) fn conn_tag() -> &'static str {
+            let conn = conn::Conn::from(conn::ConnFields { id: 1, tag: "tag" });
+            assert_eq!(conn.tag, conn::tag(&conn));
+            conn.tag
+        }
+    }
+
+    pub fn check() {
+        let socket = tcp::socket();
+        assert_eq!(socket.port, 80);
+        assert_eq!(socket.host, "localhost");
+        assert_eq!(*socket.port(), 80);
+        assert_eq!(tcp::retries(&socket), (3, 30));
+
+        let conn = tcp::conn(7);
+        assert_eq!(conn.id, 7);
+        assert_eq!(tcp::conn_tag(), "tag");
+    }
+}
+
+pub mod outer {
+    pub mod inner {
+        use ::drop_with_owned_fields::drop_with_owned_fields;
+
+        #[drop_with_owned_fields(as pub(super) struct LimitFields)]
+        pub(super) struct Limit {
+            pub(super) max: u8,
+        }
+
+        #[drop_with_owned_fields]
+        impl Drop for Limit {
+            fn drop(Self { .. }: _) {}
+        }
+    }
+
+    // `LimitFields::into()` is as visible as `Limit` (`const`, for it to be the inherent one).
+    const LIMIT: inner::Limit = inner::LimitFields { max: 42 }.into();
+
+    pub fn limit_max() -> u8 {
+        LIMIT.max
+    }
+}
+
+#[test]
+fn into_from_the_parent_module() {
+    assert_eq!(outer::limit_max(), 42);
+}
+
+#[test]
+fn nested_modules() {
+    net::check();
+}